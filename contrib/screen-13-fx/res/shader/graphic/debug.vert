@@ -0,0 +1,16 @@
+#version 460 core
+
+layout(push_constant) uniform PushConstants {
+    layout(offset = 0) mat4 view_proj;
+} push_constants;
+
+layout(location = 0) in vec3 position_in;
+layout(location = 1) in vec4 color_in;
+
+layout(location = 0) out vec4 color_out;
+
+void main() {
+    color_out = color_in;
+
+    gl_Position = push_constants.view_proj * vec4(position_in, 1);
+}
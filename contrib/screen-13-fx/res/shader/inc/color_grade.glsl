@@ -0,0 +1,16 @@
+layout(push_constant) uniform PushConstants {
+    layout(offset = 0) float exposure;
+    layout(offset = 4) float contrast;
+    layout(offset = 8) float saturation;
+} push_constants;
+
+vec3 color_adjust(vec3 color)
+{
+    color *= exp2(push_constants.exposure);
+    color = (color - 0.5) * push_constants.contrast + 0.5;
+
+    float luma = dot(color, vec3(0.2126, 0.7152, 0.0722));
+    color = mix(vec3(luma), color, push_constants.saturation);
+
+    return clamp(color, 0.0, 1.0);
+}
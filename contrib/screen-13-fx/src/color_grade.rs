@@ -0,0 +1,371 @@
+use {
+    anyhow::{bail, Context},
+    bytemuck::cast_slice,
+    inline_spirv::include_spirv,
+    log::info,
+    screen_13::prelude::*,
+    std::{ops::RangeInclusive, sync::Arc},
+};
+
+/// Range of lookup table sizes allowed by the `.cube` specification.
+const LUT_SIZES: RangeInclusive<u32> = 2..=256;
+
+/// Exposure, contrast and saturation adjustments applied before any lookup table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorAdjustments {
+    /// Exposure offset, in stops. The default value is `0.0`.
+    pub exposure: f32,
+
+    /// Contrast scale around middle grey. The default value is `1.0`.
+    pub contrast: f32,
+
+    /// Saturation scale, where `0.0` is greyscale. The default value is `1.0`.
+    pub saturation: f32,
+}
+
+impl Default for ColorAdjustments {
+    fn default() -> Self {
+        Self {
+            exposure: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+        }
+    }
+}
+
+/// Applies color adjustments and optional 3D lookup tables (LUTs) to images.
+///
+/// Lookup tables may be loaded from `.cube` files or from horizontal strip images, where each
+/// blue slice is a square placed left to right.
+#[derive(Debug)]
+pub struct ColorGrade {
+    adjust: Arc<ComputePipeline>,
+    lut: Arc<ComputePipeline>,
+    pool: HashPool,
+    pub device: Arc<Device>,
+}
+
+impl ColorGrade {
+    /// Format of lookup table images created by this type.
+    pub const LUT_FORMAT: vk::Format = vk::Format::A2B10G10R10_UNORM_PACK32;
+
+    pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
+        Ok(Self {
+            adjust: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/color_grade.comp", comp).as_slice(),
+                ),
+            )?),
+            lut: Arc::new(ComputePipeline::create(
+                device,
+                ComputePipelineInfo::default(),
+                Shader::new_compute(
+                    include_spirv!("res/shader/compute/color_grade_lut.comp", comp).as_slice(),
+                ),
+            )?),
+            pool: HashPool::new(device),
+            device: Arc::clone(device),
+        })
+    }
+
+    /// Records a pass which writes the graded contents of `image` into `dst_image`.
+    ///
+    /// `adjustments` are always applied; `lut`, if provided, is then applied to the result. The
+    /// destination image must be `R8G8B8A8_UNORM` with storage usage and the same size as `image`.
+    pub fn apply(
+        &self,
+        graph: &mut RenderGraph,
+        image: impl Into<AnyImageNode>,
+        dst_image: impl Into<AnyImageNode>,
+        lut: Option<AnyImageNode>,
+        adjustments: ColorAdjustments,
+    ) {
+        let image = image.into();
+        let dst_image = dst_image.into();
+        let dst_info = graph.node_info(dst_image);
+        let push_constants = [
+            adjustments.exposure,
+            adjustments.contrast,
+            adjustments.saturation,
+        ];
+
+        let pass = graph.begin_pass("color grade");
+        let pass = if let Some(lut) = lut {
+            pass.bind_pipeline(&self.lut)
+                .read_descriptor(0, image)
+                .read_descriptor(1, lut)
+                .write_descriptor(2, dst_image)
+        } else {
+            pass.bind_pipeline(&self.adjust)
+                .read_descriptor(0, image)
+                .write_descriptor(1, dst_image)
+        };
+
+        pass.record_compute(move |compute, _| {
+            compute
+                .push_constants(cast_slice(&push_constants))
+                .dispatch(dst_info.width.div_ceil(8), dst_info.height.div_ceil(8), 1);
+        });
+    }
+
+    fn create_lut(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        size: u32,
+        texels: impl ExactSizeIterator<Item = [f32; 3]>,
+    ) -> anyhow::Result<Arc<Image>> {
+        let texel_count = (size * size * size) as usize;
+
+        debug_assert_eq!(texels.len(), texel_count);
+
+        let mut render_graph = RenderGraph::new();
+        let image = render_graph.bind_node(Arc::new(
+            Image::create(
+                &self.device,
+                ImageInfo::image_3d(
+                    size,
+                    size,
+                    size,
+                    Self::LUT_FORMAT,
+                    vk::ImageUsageFlags::SAMPLED | vk::ImageUsageFlags::TRANSFER_DST,
+                ),
+            )
+            .context("Unable to create LUT image")?,
+        ));
+
+        let texel_buf_len = (texel_count * 4) as vk::DeviceSize;
+        let mut texel_buf = self.pool.lease(BufferInfo::host_mem(
+            texel_buf_len,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+        ))?;
+
+        {
+            let texel_buf =
+                &mut Buffer::mapped_slice_mut(&mut texel_buf)[0..texel_buf_len as usize];
+
+            for (dst, [r, g, b]) in texel_buf.chunks_exact_mut(4).zip(texels) {
+                let unorm = |val: f32| (val.clamp(0.0, 1.0) * 1023.0).round() as u32;
+                let packed = (0b11 << 30) | (unorm(b) << 20) | (unorm(g) << 10) | unorm(r);
+
+                dst.copy_from_slice(&packed.to_ne_bytes());
+            }
+        }
+
+        let texel_buf = render_graph.bind_node(texel_buf);
+        render_graph.copy_buffer_to_image(texel_buf, image);
+
+        let image = render_graph.unbind_node(image);
+
+        render_graph
+            .resolve()
+            .submit(&mut self.pool, queue_family_index, queue_index)?;
+
+        Ok(image)
+    }
+
+    /// Loads a 3D lookup table from the contents of an Adobe/Resolve `.cube` file.
+    ///
+    /// Only 3D tables using the default `0.0` to `1.0` domain are supported.
+    pub fn load_cube_lut(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        cube: &str,
+    ) -> anyhow::Result<Arc<Image>> {
+        let (size, texels) = parse_cube_lut(cube)?;
+
+        info!("loading {size}x{size}x{size} cube LUT");
+
+        // Cube files list red fastest, then green, then blue; this is the 3D image texel order
+        self.create_lut(queue_family_index, queue_index, size, texels.into_iter())
+    }
+
+    /// Loads a 3D lookup table from an `RGBA8` strip image which is `size * size` texels wide and
+    /// `size` texels tall.
+    pub fn load_strip_lut(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        pixels: &[u8],
+        size: u32,
+    ) -> anyhow::Result<Arc<Image>> {
+        if !LUT_SIZES.contains(&size) {
+            bail!("Unsupported LUT size {size}");
+        }
+
+        let size_usize = size as usize;
+
+        if pixels.len() < 4 * size_usize.pow(3) {
+            bail!("Insufficient LUT strip data");
+        }
+
+        info!("loading {size}x{size}x{size} strip LUT");
+
+        let stride = size_usize * size_usize;
+        let texels = (0..size_usize.pow(3)).map(|idx| {
+            let (r, g, b) = (
+                idx % size_usize,
+                idx / size_usize % size_usize,
+                idx / stride,
+            );
+            let offset = 4 * (g * stride + b * size_usize + r);
+            let unorm = |val: u8| val as f32 / 255.0;
+
+            [
+                unorm(pixels[offset]),
+                unorm(pixels[offset + 1]),
+                unorm(pixels[offset + 2]),
+            ]
+        });
+
+        self.create_lut(queue_family_index, queue_index, size, texels)
+    }
+}
+
+/// Parses the contents of a `.cube` file into its size and texels, in file order.
+fn parse_cube_lut(cube: &str) -> anyhow::Result<(u32, Vec<[f32; 3]>)> {
+    let mut size = None;
+    let mut texels = vec![];
+
+    for (line_idx, line) in cube.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with("TITLE") {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let keyword = tokens.next().unwrap_or_default();
+
+        match keyword {
+            "LUT_3D_SIZE" => {
+                size = Some(
+                    tokens
+                        .next()
+                        .and_then(|size| size.parse::<u32>().ok())
+                        .filter(|size| LUT_SIZES.contains(size))
+                        .with_context(|| format!("Invalid LUT size on line {}", line_idx + 1))?,
+                );
+            }
+            "LUT_1D_SIZE" => bail!("1D LUTs are not supported"),
+            "DOMAIN_MIN" | "DOMAIN_MAX" | "LUT_3D_INPUT_RANGE" => {
+                let expected = match keyword {
+                    "DOMAIN_MIN" => [0.0].as_slice(),
+                    "DOMAIN_MAX" => &[1.0],
+                    _ => &[0.0, 1.0],
+                };
+                let values = tokens
+                    .map(str::parse::<f32>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok();
+
+                // Domains give one value per channel; the input range is a single min/max pair
+                let is_default = values.is_some_and(|values| {
+                    !values.is_empty()
+                        && values.chunks(expected.len()).all(|chunk| chunk == expected)
+                });
+
+                if !is_default {
+                    bail!("Custom LUT domains are not supported");
+                }
+            }
+            _ => {
+                let texel = [keyword]
+                    .into_iter()
+                    .chain(tokens)
+                    .map(str::parse::<f32>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .ok()
+                    .and_then(|texel| <[f32; 3]>::try_from(texel).ok())
+                    .with_context(|| format!("Invalid LUT entry on line {}", line_idx + 1))?;
+
+                texels.push(texel);
+            }
+        }
+    }
+
+    let size = size.context("Missing LUT_3D_SIZE")?;
+    let texel_count = size.checked_pow(3).context("LUT size overflow")? as usize;
+
+    if texels.len() != texel_count {
+        bail!("Expected {texel_count} LUT entries, found {}", texels.len());
+    }
+
+    Ok((size, texels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity_cube(size: u32) -> String {
+        let mut res = format!("TITLE \"identity\"\nLUT_3D_SIZE {size}\n");
+        let max = (size - 1) as f32;
+
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    res += &format!("{} {} {}\n", r as f32 / max, g as f32 / max, b as f32 / max);
+                }
+            }
+        }
+
+        res
+    }
+
+    #[test]
+    pub fn cube_lut() {
+        let (size, texels) = parse_cube_lut(&identity_cube(2)).unwrap();
+
+        assert_eq!(size, 2);
+        assert_eq!(texels.len(), 8);
+        assert_eq!(texels[0], [0.0, 0.0, 0.0]);
+        assert_eq!(texels[1], [1.0, 0.0, 0.0]);
+        assert_eq!(texels[2], [0.0, 1.0, 0.0]);
+        assert_eq!(texels[7], [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    pub fn cube_lut_default_domain() {
+        let cube = identity_cube(2).replace(
+            "LUT_3D_SIZE 2\n",
+            "LUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 1 1 1\nLUT_3D_INPUT_RANGE 0 1\n",
+        );
+
+        assert!(parse_cube_lut(&cube).is_ok());
+    }
+
+    #[test]
+    pub fn cube_lut_custom_domain() {
+        let cube = identity_cube(2).replace("LUT_3D_SIZE 2\n", "LUT_3D_SIZE 2\nDOMAIN_MAX 2 2 2\n");
+
+        assert!(parse_cube_lut(&cube).is_err());
+
+        let cube = identity_cube(2).replace(
+            "LUT_3D_SIZE 2\n",
+            "LUT_3D_SIZE 2\nLUT_3D_INPUT_RANGE -1 1\n",
+        );
+
+        assert!(parse_cube_lut(&cube).is_err());
+    }
+
+    #[test]
+    pub fn cube_lut_entry_count() {
+        let cube = identity_cube(2);
+        let cube = cube.trim_end().rsplit_once('\n').unwrap().0;
+
+        assert!(parse_cube_lut(cube).is_err());
+    }
+
+    #[test]
+    pub fn cube_lut_size() {
+        assert!(parse_cube_lut("LUT_3D_SIZE 1\n0 0 0\n").is_err());
+        assert!(parse_cube_lut("LUT_3D_SIZE 257\n").is_err());
+        assert!(parse_cube_lut("LUT_3D_SIZE 4294967295\n").is_err());
+        assert!(parse_cube_lut("LUT_3D_SIZE two\n").is_err());
+        assert!(parse_cube_lut("0 0 0\n").is_err());
+    }
+}
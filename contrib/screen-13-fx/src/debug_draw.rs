@@ -0,0 +1,191 @@
+use {
+    anyhow::Context,
+    bytemuck::cast_slice,
+    glam::{Mat4, Vec3},
+    inline_spirv::include_spirv,
+    screen_13::prelude::*,
+    std::sync::Arc,
+};
+
+type Color = [f32; 4];
+
+/// Column-major 4x4 matrix, such as the result of `glam::Mat4::to_cols_array`.
+type Matrix = [f32; 16];
+
+/// Position (xyz).
+type Point = [f32; 3];
+
+/// Position (xyz) followed by color (rgba), matching the vertex input of `debug.vert`.
+type Vertex = [f32; 7];
+
+/// Accumulates immediate-mode debug lines which are drawn as an overlay on top of an image.
+///
+/// Geometry is recorded in world space and drawn using the view-projection transform given when
+/// calling [`DebugDraw::draw`] or [`DebugDraw::draw_depth`]. All accumulated geometry is consumed
+/// by each draw, so it must be recorded again for the next frame.
+///
+/// Points and matrices are plain arrays so that callers are not tied to any particular version of a
+/// math library.
+#[derive(Debug)]
+pub struct DebugDraw {
+    cache: HashPool,
+    pipeline: Arc<GraphicPipeline>,
+    vertices: Vec<Vertex>,
+}
+
+impl DebugDraw {
+    pub fn new(device: &Arc<Device>) -> anyhow::Result<Self> {
+        let cache = HashPool::new(device);
+        let pipeline = Arc::new(
+            GraphicPipeline::create(
+                device,
+                GraphicPipelineInfoBuilder::default()
+                    .blend(BlendMode::ALPHA)
+                    .cull_mode(vk::CullModeFlags::NONE)
+                    .topology(vk::PrimitiveTopology::LINE_LIST),
+                [
+                    Shader::new_vertex(
+                        include_spirv!("res/shader/graphic/debug.vert", vert).as_slice(),
+                    ),
+                    Shader::new_fragment(
+                        include_spirv!("res/shader/graphic/debug.frag", frag).as_slice(),
+                    ),
+                ],
+            )
+            .context("Unable to create debug draw pipeline")?,
+        );
+
+        Ok(Self {
+            cache,
+            pipeline,
+            vertices: vec![],
+        })
+    }
+
+    /// Adds the twelve edges of an axis-aligned bounding box.
+    pub fn aabb(&mut self, min: Point, max: Point, color: impl Into<Color>) -> &mut Self {
+        let color = color.into();
+        let corner = |x: bool, y: bool, z: bool| {
+            [
+                if x { max[0] } else { min[0] },
+                if y { max[1] } else { min[1] },
+                if z { max[2] } else { min[2] },
+            ]
+        };
+
+        for a in [false, true] {
+            for b in [false, true] {
+                self.line(corner(false, a, b), corner(true, a, b), color);
+                self.line(corner(a, false, b), corner(a, true, b), color);
+                self.line(corner(a, b, false), corner(a, b, true), color);
+            }
+        }
+
+        self
+    }
+
+    /// Adds a red, green, and blue line for the x, y, and z axes of the given transform.
+    pub fn axes(&mut self, transform: Matrix, size: f32) -> &mut Self {
+        let transform = Mat4::from_cols_array(&transform);
+        let point = |axis: Vec3| transform.transform_point3(axis * size).to_array();
+        let origin = point(Vec3::ZERO);
+
+        self.line(origin, point(Vec3::X), [1.0, 0.0, 0.0, 1.0])
+            .line(origin, point(Vec3::Y), [0.0, 1.0, 0.0, 1.0])
+            .line(origin, point(Vec3::Z), [0.0, 0.0, 1.0, 1.0])
+    }
+
+    /// Removes all accumulated geometry without drawing it.
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+
+    /// Draws all accumulated geometry on top of `image`, without depth testing.
+    pub fn draw(
+        &mut self,
+        graph: &mut RenderGraph,
+        image: impl Into<AnyImageNode>,
+        view_proj: Matrix,
+    ) {
+        self.record(graph, image.into(), None, view_proj);
+    }
+
+    /// Draws all accumulated geometry on top of `image`, hiding lines which are behind the depth
+    /// values already stored in `depth_image`.
+    ///
+    /// The depth image is only read; debug geometry does not write depth.
+    pub fn draw_depth(
+        &mut self,
+        graph: &mut RenderGraph,
+        image: impl Into<AnyImageNode>,
+        depth_image: impl Into<AnyImageNode>,
+        view_proj: Matrix,
+    ) {
+        self.record(graph, image.into(), Some(depth_image.into()), view_proj);
+    }
+
+    /// Returns `true` if no geometry has been accumulated since the last draw.
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Adds a line segment from `a` to `b`.
+    pub fn line(&mut self, a: Point, b: Point, color: impl Into<Color>) -> &mut Self {
+        let [r, g, blue, alpha] = color.into();
+
+        self.vertices.push([a[0], a[1], a[2], r, g, blue, alpha]);
+        self.vertices.push([b[0], b[1], b[2], r, g, blue, alpha]);
+
+        self
+    }
+
+    fn record(
+        &mut self,
+        graph: &mut RenderGraph,
+        image: AnyImageNode,
+        depth_image: Option<AnyImageNode>,
+        view_proj: Matrix,
+    ) {
+        if self.vertices.is_empty() {
+            return;
+        }
+
+        let vertex_count = self.vertices.len() as u32;
+        let vertex_data: &[u8] = cast_slice(&self.vertices);
+        let vertex_buf_len = vertex_data.len() as vk::DeviceSize;
+        let mut vertex_buf = self
+            .cache
+            .lease(BufferInfo::host_mem(
+                vertex_buf_len,
+                vk::BufferUsageFlags::VERTEX_BUFFER,
+            ))
+            .unwrap();
+
+        Buffer::mapped_slice_mut(&mut vertex_buf)[0..vertex_buf_len as usize]
+            .copy_from_slice(vertex_data);
+
+        self.vertices.clear();
+
+        let vertex_buf = graph.bind_node(vertex_buf);
+
+        let mut pass = graph
+            .begin_pass("debug draw")
+            .bind_pipeline(&self.pipeline)
+            .access_node(vertex_buf, AccessType::VertexBuffer)
+            .load_color(0, image)
+            .store_color(0, image);
+
+        if let Some(depth_image) = depth_image {
+            pass = pass
+                .set_depth_stencil(DepthStencilMode::DEPTH_READ)
+                .load_depth_stencil(depth_image);
+        }
+
+        pass.record_subpass(move |subpass, _| {
+            subpass
+                .push_constants(cast_slice(&view_proj))
+                .bind_vertex_buffer(vertex_buf)
+                .draw(vertex_count, 1, 0, 0);
+        });
+    }
+}
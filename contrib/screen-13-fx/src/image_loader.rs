@@ -1,6 +1,11 @@
 use {
-    super::BitmapFont, anyhow::Context, bmfont::BMFont, inline_spirv::include_spirv, log::info,
-    screen_13::prelude::*, std::sync::Arc,
+    super::BitmapFont,
+    anyhow::{bail, Context},
+    bmfont::BMFont,
+    inline_spirv::include_spirv,
+    log::info,
+    screen_13::{driver::format_texel_block_size, prelude::*},
+    std::sync::Arc,
 };
 
 #[cfg(debug_assertions)]
@@ -29,7 +34,6 @@ impl ImageFormat {
 #[derive(Debug)]
 pub struct ImageLoader {
     pool: HashPool,
-    _decode_r_rg: Arc<ComputePipeline>,
     decode_rgb_rgba: Arc<ComputePipeline>,
     pub device: Arc<Device>,
 }
@@ -38,13 +42,6 @@ impl ImageLoader {
     pub fn new(device: &Arc<Device>) -> Result<Self, DriverError> {
         Ok(Self {
             pool: HashPool::new(device),
-            _decode_r_rg: Arc::new(ComputePipeline::create(
-                device,
-                ComputePipelineInfo::default(),
-                Shader::new_compute(
-                    include_spirv!("res/shader/compute/decode_bitmap_r_rg.comp", comp).as_slice(),
-                ),
-            )?),
             decode_rgb_rgba: Arc::new(ComputePipeline::create(
                 device,
                 ComputePipelineInfo::default(),
@@ -64,9 +61,17 @@ impl ImageLoader {
         height: u32,
         is_srgb: bool,
         is_temporary: bool,
+        mip_level_count: u32,
     ) -> anyhow::Result<Arc<Image>> {
         let format = match format {
-            ImageFormat::R8 | ImageFormat::R8G8 => {
+            ImageFormat::R8 => {
+                if is_srgb {
+                    bail!("Unsupported format: R8_SRGB");
+                } else {
+                    vk::Format::R8_UNORM
+                }
+            }
+            ImageFormat::R8G8 => {
                 if is_temporary {
                     vk::Format::R8G8_UINT
                 } else if is_srgb {
@@ -98,7 +103,9 @@ impl ImageLoader {
         Ok(Arc::new(
             Image::create(
                 &self.device,
-                ImageInfo::image_2d(width, height, format, usage),
+                ImageInfo::image_2d(width, height, format, usage)
+                    .to_builder()
+                    .mip_level_count(mip_level_count),
             )
             .context("Unable to create new image")?,
         ))
@@ -114,6 +121,58 @@ impl ImageLoader {
         width: u32,
         height: u32,
         is_srgb: bool,
+    ) -> anyhow::Result<Arc<Image>> {
+        self.decode(
+            queue_family_index,
+            queue_index,
+            pixels,
+            format,
+            width,
+            height,
+            is_srgb,
+            1,
+        )
+    }
+
+    /// Decodes a bitmap into an image with a complete mip chain, which is generated from the
+    /// decoded pixels using linear blits.
+    ///
+    /// Sampling the returned image with a mipmap mode of `vk::SamplerMipmapMode::LINEAR` provides
+    /// trilinear filtering.
+    #[allow(clippy::too_many_arguments)]
+    pub fn decode_bitmap_mipmapped(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        pixels: &[u8],
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        is_srgb: bool,
+    ) -> anyhow::Result<Arc<Image>> {
+        self.decode(
+            queue_family_index,
+            queue_index,
+            pixels,
+            format,
+            width,
+            height,
+            is_srgb,
+            mip_level_count(width, height),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn decode(
+        &mut self,
+        queue_family_index: usize,
+        queue_index: usize,
+        pixels: &[u8],
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        is_srgb: bool,
+        mip_level_count: u32,
     ) -> anyhow::Result<Arc<Image>> {
         info!(
             "decoding {}x{} {:?} bitmap ({} K)",
@@ -134,16 +193,17 @@ impl ImageLoader {
         }
 
         let mut render_graph = RenderGraph::new();
-        let image =
-            render_graph.bind_node(self.create_image(format, width, height, is_srgb, false)?);
+        let image = render_graph.bind_node(self.create_image(
+            format,
+            width,
+            height,
+            is_srgb,
+            false,
+            mip_level_count,
+        )?);
 
         // Fill the image from the temporary buffer
         match format {
-            ImageFormat::R8 => {
-                // This format requires a conversion
-                info!("Converting R to RG");
-                todo!()
-            }
             ImageFormat::R8G8B8 => {
                 // This format requires a conversion
                 //info!("Converting RGB to RGBA");
@@ -184,8 +244,8 @@ impl ImageLoader {
 
                 // We create a temporary storage image because SRGB support isn't wide enough to
                 // have SRGB storage images directly
-                let temp_image =
-                    render_graph.bind_node(self.create_image(format, width, height, false, true)?);
+                let temp_image = render_graph
+                    .bind_node(self.create_image(format, width, height, false, true, 1)?);
 
                 // Copy host-local data in the buffer to the temporary buffer on the GPU and then
                 // use a compute shader to decode it before copying it over the output image
@@ -205,7 +265,7 @@ impl ImageLoader {
                     .submit_pass()
                     .copy_image(temp_image, image);
             }
-            ImageFormat::R8G8 | ImageFormat::R8G8B8A8 => {
+            ImageFormat::R8 | ImageFormat::R8G8 | ImageFormat::R8G8B8A8 => {
                 // Lease a temporary buffer from the pool
                 let mut pixel_buf = self.pool.lease(BufferInfo::host_mem(
                     pixels.len() as _,
@@ -223,6 +283,8 @@ impl ImageLoader {
             }
         }
 
+        render_graph.generate_mip_levels(image, vk::Filter::LINEAR);
+
         let image = render_graph.unbind_node(image);
 
         render_graph
@@ -272,6 +334,101 @@ impl ImageLoader {
         )
     }
 
+    /// Records a copy of new pixel data into a region of an existing image, such as one
+    /// previously returned by [`ImageLoader::decode_bitmap`].
+    ///
+    /// The pixel data is staged in a buffer leased from this loader, so repeated updates (video
+    /// frames, dynamic atlases) reuse the same staging memory instead of creating new images.
+    ///
+    /// Only mip level `0` is updated; use [`RenderGraph::generate_mip_levels`] afterwards to update
+    /// the remaining levels of a mipmapped image.
+    ///
+    /// Returns an error if `pixels` holds less than `width * height` pixels of `format`, if the
+    /// region does not fit within the image, or if the image format does not store `format`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_bitmap(
+        &mut self,
+        render_graph: &mut RenderGraph,
+        image: impl Into<AnyImageNode>,
+        pixels: &[u8],
+        format: ImageFormat,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> anyhow::Result<()> {
+        let image = image.into();
+        let image_info = render_graph.node_info(image);
+
+        check_region(&image_info, x, y, width, height)?;
+
+        // R8G8B8 images are stored as R8G8B8A8 so the staging data must be expanded to match
+        let texel_size = match format {
+            ImageFormat::R8G8B8 => 4,
+            ImageFormat::R8 | ImageFormat::R8G8 | ImageFormat::R8G8B8A8 => format.stride(),
+        };
+
+        if format_texel_block_size(image_info.fmt) as usize != texel_size {
+            bail!(
+                "Image format {:?} does not match {format:?}",
+                image_info.fmt
+            );
+        }
+
+        let pixel_count = width as usize * height as usize;
+        let pixels_len = format
+            .stride()
+            .checked_mul(pixel_count)
+            .context("Region too large")?;
+
+        if pixels.len() < pixels_len {
+            bail!("Insufficient data: {} of {pixels_len} bytes", pixels.len());
+        }
+
+        let pixel_buf_len = texel_size
+            .checked_mul(pixel_count)
+            .context("Region too large")?;
+        let mut pixel_buf = self.pool.lease(BufferInfo::host_mem(
+            pixel_buf_len as _,
+            vk::BufferUsageFlags::TRANSFER_SRC,
+        ))?;
+
+        {
+            let pixel_buf = &mut Buffer::mapped_slice_mut(&mut pixel_buf)[0..pixel_buf_len];
+
+            if let ImageFormat::R8G8B8 = format {
+                expand_rgb_rgba(pixels, pixel_buf);
+            } else {
+                pixel_buf.copy_from_slice(&pixels[0..pixel_buf_len]);
+            }
+        }
+
+        let pixel_buf = render_graph.bind_node(pixel_buf);
+        render_graph.copy_buffer_to_image_region(
+            pixel_buf,
+            image,
+            vk::BufferImageCopy {
+                buffer_offset: 0,
+                buffer_row_length: width,
+                buffer_image_height: height,
+                image_subresource: vk::ImageSubresourceLayers {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    mip_level: 0,
+                    base_array_layer: 0,
+                    layer_count: 1,
+                },
+                image_offset: vk::Offset3D { x, y, z: 0 },
+                image_extent: vk::Extent3D {
+                    width,
+                    height,
+                    depth: 1,
+                },
+            },
+        );
+
+        Ok(())
+    }
+
     pub fn load_bitmap_font<'a>(
         &mut self,
         queue_family_index: usize,
@@ -296,3 +453,79 @@ impl ImageLoader {
         BitmapFont::new(&self.device, font, pages)
     }
 }
+
+/// Returns the number of mip levels in a complete mip chain for an image of the given size.
+fn mip_level_count(width: u32, height: u32) -> u32 {
+    u32::BITS - width.max(height).max(1).leading_zeros()
+}
+
+/// Returns an error if the region does not fit within the image.
+fn check_region(
+    image_info: &ImageInfo,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+) -> anyhow::Result<()> {
+    let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) else {
+        bail!("Region out of bounds");
+    };
+
+    let fits =
+        |offset: u32, len: u32, size: u32| offset.checked_add(len).is_some_and(|end| end <= size);
+
+    if !fits(x, width, image_info.width) || !fits(y, height, image_info.height) {
+        bail!("Region out of bounds");
+    }
+
+    Ok(())
+}
+
+/// Expands tightly packed RGB pixels into RGBA pixels with an opaque alpha channel.
+fn expand_rgb_rgba(src: &[u8], dst: &mut [u8]) {
+    for (dst, src) in dst.chunks_exact_mut(4).zip(src.chunks_exact(3)) {
+        dst[0..3].copy_from_slice(src);
+        dst[3] = u8::MAX;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_region_bounds() {
+        let image_info = ImageInfo::image_2d(
+            4,
+            4,
+            vk::Format::R8G8B8A8_UNORM,
+            vk::ImageUsageFlags::TRANSFER_DST,
+        );
+
+        assert!(check_region(&image_info, 0, 0, 4, 4).is_ok());
+        assert!(check_region(&image_info, 1, 2, 3, 2).is_ok());
+        assert!(check_region(&image_info, -1, 0, 1, 1).is_err());
+        assert!(check_region(&image_info, 0, -1, 1, 1).is_err());
+        assert!(check_region(&image_info, 1, 0, 4, 1).is_err());
+        assert!(check_region(&image_info, 0, 4, 1, 1).is_err());
+        assert!(check_region(&image_info, 1, 1, u32::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn mip_level_count_sizes() {
+        assert_eq!(mip_level_count(1, 1), 1);
+        assert_eq!(mip_level_count(2, 1), 2);
+        assert_eq!(mip_level_count(256, 256), 9);
+        assert_eq!(mip_level_count(257, 16), 9);
+        assert_eq!(mip_level_count(640, 480), 10);
+    }
+
+    #[test]
+    fn expand_rgb_rgba_pixels() {
+        let mut rgba = [0; 8];
+
+        expand_rgb_rgba(&[1, 2, 3, 4, 5, 6], &mut rgba);
+
+        assert_eq!(rgba, [1, 2, 3, 255, 4, 5, 6, 255]);
+    }
+}
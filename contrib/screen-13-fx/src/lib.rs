@@ -1,17 +1,21 @@
 pub mod prelude {
     pub use super::{
-        BitmapFont, BitmapGlyphColor, ComputePresenter, GraphicPresenter, ImageFormat, ImageLoader,
-        Transition, TransitionPipeline,
+        BitmapFont, BitmapGlyphColor, ColorAdjustments, ColorGrade, ComputePresenter, DebugDraw,
+        GraphicPresenter, ImageFormat, ImageLoader, Transition, TransitionPipeline,
     };
 }
 
 mod bitmap_font;
+mod color_grade;
+mod debug_draw;
 mod image_loader;
 mod presenter;
 mod transition;
 
 pub use self::{
     bitmap_font::{BitmapFont, BitmapGlyphColor},
+    color_grade::{ColorAdjustments, ColorGrade},
+    debug_draw::DebugDraw,
     image_loader::{ImageFormat, ImageLoader},
     presenter::{ComputePresenter, GraphicPresenter},
     transition::{Transition, TransitionPipeline},
@@ -104,7 +104,7 @@ impl ImGui {
                         | vk::ImageUsageFlags::TRANSFER_SRC, // TODO: Make TRANSFER_SRC an "extra flags"
                 ))
                 .unwrap();
-            image.as_mut().name = Some("ImGui Output".to_string());
+            Image::set_name(image.as_mut(), "ImGui Output");
 
             image
         });
@@ -279,7 +279,7 @@ impl ImGui {
                         | vk::ImageUsageFlags::TRANSFER_DST,
                 ))
                 .unwrap();
-            image.as_mut().name = Some("ImGui Font Atlas".to_string());
+            Image::set_name(image.as_mut(), "ImGui Font Atlas");
 
             image
         });
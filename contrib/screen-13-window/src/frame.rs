@@ -1,12 +1,35 @@
 use {
+    log::warn,
     screen_13::{
         driver::device::Device,
         graph::{node::SwapchainImageNode, RenderGraph},
     },
     std::sync::Arc,
-    winit::{dpi::PhysicalPosition, event::Event, window::Window},
+    winit::{
+        dpi::PhysicalPosition,
+        event::Event,
+        event_loop::ActiveEventLoop,
+        window::{BadImage, Cursor, CursorGrabMode, CustomCursor, Window},
+    },
 };
 
+/// Creates a mouse cursor image from `RGBA8` pixels, where `hotspot_x` and `hotspot_y` locate the
+/// point of the cursor which is used for clicking.
+///
+/// The returned cursor is shown using [`set_cursor`] and may be reused.
+pub fn create_cursor(
+    event_loop: &ActiveEventLoop,
+    pixels: &[u8],
+    width: u16,
+    height: u16,
+    hotspot_x: u16,
+    hotspot_y: u16,
+) -> Result<CustomCursor, BadImage> {
+    let source = CustomCursor::from_rgba(pixels, width, height, hotspot_x, hotspot_y)?;
+
+    Ok(event_loop.create_custom_cursor(source))
+}
+
 /// Centers the mouse cursor within the window.
 pub fn center_cursor(window: &Window) {
     let window_size = window.inner_size();
@@ -15,18 +38,53 @@ pub fn center_cursor(window: &Window) {
     set_cursor_position(window, x, y);
 }
 
+/// Sets the mouse cursor shown while it is over the window, such as an icon or an image from
+/// [`create_cursor`].
+pub fn set_cursor(window: &Window, cursor: impl Into<Cursor>) {
+    window.set_cursor(cursor);
+}
+
 /// Sets the mouse cursor at the specified position within the window.
 pub fn set_cursor_position(window: &Window, x: u32, y: u32) {
     let position = PhysicalPosition::new(x as i32, y as i32);
     window.set_cursor_position(position).unwrap_or_default();
 }
 
+/// Confines the mouse cursor to the window, preferring to lock it in place where supported.
+///
+/// While grabbed, relative mouse motion is still reported by `DeviceEvent::MouseMotion` events.
+pub fn set_cursor_grab(window: &Window, grab: bool) {
+    let res = if grab {
+        window
+            .set_cursor_grab(CursorGrabMode::Locked)
+            .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined))
+    } else {
+        window.set_cursor_grab(CursorGrabMode::None)
+    };
+
+    if let Err(err) = res {
+        warn!("unable to set cursor grab: {err}");
+    }
+}
+
+/// Shows or hides the mouse cursor while it is over the window.
+pub fn set_cursor_visible(window: &Window, visible: bool) {
+    window.set_cursor_visible(visible);
+}
+
 /// A request to render a single frame to the provided render graph.
 pub struct FrameContext<'a> {
     /// The device this frame belongs to.
     pub device: &'a Arc<Device>,
 
+    pub(super) event_loop: &'a ActiveEventLoop,
+
     /// A slice of events that have occurred since the previous frame.
+    ///
+    /// Device events are discarded while frames are paused using [`BackgroundMode::Pause`], and
+    /// only the most recent events are kept when many occur between frames.
+    ///
+    /// [`BackgroundMode::Pause`]: crate::BackgroundMode::Pause
     pub events: &'a [Event<()>],
 
     /// The height, in pixels, of the current frame.
@@ -66,8 +124,43 @@ impl FrameContext<'_> {
         center_cursor(self.window);
     }
 
+    /// Creates a mouse cursor image from `RGBA8` pixels, where `hotspot_x` and `hotspot_y` locate
+    /// the point of the cursor which is used for clicking.
+    ///
+    /// The returned cursor is shown using [`FrameContext::set_cursor`] and should be kept for reuse
+    /// instead of being created each frame.
+    pub fn create_cursor(
+        &self,
+        pixels: &[u8],
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<CustomCursor, BadImage> {
+        create_cursor(self.event_loop, pixels, width, height, hotspot_x, hotspot_y)
+    }
+
+    /// Sets the mouse cursor shown while it is over the window, such as an icon or an image from
+    /// [`FrameContext::create_cursor`].
+    pub fn set_cursor(&self, cursor: impl Into<Cursor>) {
+        set_cursor(self.window, cursor);
+    }
+
     /// Sets the mouse cursor at the specified position within the window.
     pub fn set_cursor_position(&self, x: u32, y: u32) {
         set_cursor_position(self.window, x, y);
     }
+
+    /// Confines the mouse cursor to the window, preferring to lock it in place where supported.
+    ///
+    /// Useful for first-person camera controls, which should read relative motion from
+    /// `DeviceEvent::MouseMotion` events.
+    pub fn set_cursor_grab(&self, grab: bool) {
+        set_cursor_grab(self.window, grab);
+    }
+
+    /// Shows or hides the mouse cursor while it is over the window.
+    pub fn set_cursor_visible(&self, visible: bool) {
+        set_cursor_visible(self.window, visible);
+    }
 }
@@ -7,7 +7,7 @@ use {
     screen_13::{
         driver::{
             ash::vk,
-            device::{Device, DeviceInfo},
+            device::{Device, DeviceInfo, DeviceInfoBuilder},
             surface::Surface,
             swapchain::{Swapchain, SwapchainInfo},
             DriverError,
@@ -16,17 +16,46 @@ use {
         pool::hash::HashPool,
         Display, DisplayError, DisplayInfoBuilder,
     },
-    std::{error, fmt, sync::Arc},
+    std::{
+        error, fmt,
+        hint::spin_loop,
+        sync::Arc,
+        thread::sleep,
+        time::{Duration, Instant},
+    },
     winit::{
         application::ApplicationHandler,
         error::EventLoopError,
         event::{DeviceEvent, DeviceId, Event, WindowEvent},
-        event_loop::{ActiveEventLoop, EventLoop},
+        event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
         monitor::MonitorHandle,
         window::{WindowAttributes, WindowId},
     },
 };
 
+/// Describes how frames are drawn while the window is in the background.
+///
+/// A window is in the background while it is minimized or fully hidden and, if enabled using
+/// [`WindowBuilder::background_when_unfocused`], while it does not have keyboard focus. Use
+/// [`WindowBuilder::background_fn`] to be notified so that the program may pause its own
+/// simulation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BackgroundMode {
+    /// Frames continue to be drawn at the normal rate.
+    #[default]
+    Continue,
+
+    /// No frames are drawn until the window returns to the foreground.
+    ///
+    /// Device events, such as raw mouse motion, are discarded while paused.
+    Pause,
+
+    /// Frames are drawn at no more than the given rate, in frames per second.
+    ///
+    /// Rates which are not positive are ignored and frames continue to be drawn at the normal rate.
+    Throttle(f32),
+}
+
 /// Describes a screen mode for display.
 #[derive(Clone, Copy, Debug)]
 pub enum FullscreenMode {
@@ -53,6 +82,12 @@ impl Window {
         WindowBuilder::default()
     }
 
+    /// Runs the event loop, calling `draw_fn` for each frame until the window is closed.
+    ///
+    /// If the device is lost while drawing, the device and display are recreated for the existing
+    /// window and the function set using [`WindowBuilder::device_lost_fn`] is called so that the
+    /// program may load its resources again. Without that function this returns
+    /// `WindowError::Driver(DriverError::DeviceLost)` instead.
     pub fn run<F>(self, draw_fn: F) -> Result<(), WindowError>
     where
         F: FnMut(FrameContext),
@@ -115,6 +150,55 @@ impl Window {
                 Ok(display)
             }
 
+            /// Replaces a lost device, and the display of the active window, and then calls the
+            /// device lost function with the new device.
+            fn recreate_device(&mut self) -> Result<(), DriverError> {
+                if let Some(ActiveWindow {
+                    display,
+                    display_pool,
+                    events,
+                    is_focused,
+                    is_minimized,
+                    is_occluded,
+                    next_frame,
+                    window,
+                    ..
+                }) = self.active_window.take()
+                {
+                    // The window may only have one swapchain at a time
+                    drop(display);
+                    drop(display_pool);
+
+                    self.device = Arc::new(Device::create_display(
+                        DeviceInfoBuilder::default().debug(self.data.debug),
+                        &window,
+                    )?);
+
+                    let display = self.create_display(&window)?;
+                    let display_pool = HashPool::new(&self.device);
+
+                    self.active_window = Some(ActiveWindow {
+                        display,
+                        display_pool,
+                        display_resize: None,
+                        events,
+                        is_focused,
+                        is_minimized,
+                        is_occluded,
+                        next_frame,
+                        window,
+                    });
+                }
+
+                info!("recreated device");
+
+                if let Some(f) = self.data.device_lost_fn.as_mut() {
+                    f(&self.device);
+                }
+
+                Ok(())
+            }
+
             fn window_mode_attributes(
                 &self,
                 attributes: WindowAttributes,
@@ -187,9 +271,18 @@ impl Window {
         where
             F: FnMut(FrameContext),
         {
-            fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
-                if let Some(ActiveWindow { window, .. }) = self.active_window.as_ref() {
-                    window.request_redraw();
+            fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+                if let Some(active_window) = self.active_window.as_ref() {
+                    if let Some(control_flow) = self.data.background_wait(
+                        active_window.is_background(self.data.background_unfocused),
+                        active_window.next_frame,
+                        Instant::now(),
+                    ) {
+                        event_loop.set_control_flow(control_flow);
+                    } else {
+                        event_loop.set_control_flow(ControlFlow::Wait);
+                        active_window.window.request_redraw();
+                    }
                 }
             }
 
@@ -199,8 +292,14 @@ impl Window {
                 device_id: DeviceId,
                 event: DeviceEvent,
             ) {
-                if let Some(ActiveWindow { events, .. }) = self.active_window.as_mut() {
-                    events.push(Event::DeviceEvent { device_id, event });
+                if let Some(active_window) = self.active_window.as_mut() {
+                    let is_background = active_window.is_background(self.data.background_unfocused);
+
+                    self.data.buffer_event(
+                        &mut active_window.events,
+                        Event::DeviceEvent { device_id, event },
+                        is_background,
+                    );
                 }
             }
 
@@ -241,13 +340,23 @@ impl Window {
                     display_pool,
                     display_resize: None,
                     events: vec![],
+                    is_focused: true,
+                    is_minimized: false,
+                    is_occluded: false,
+                    next_frame: Instant::now(),
                     window,
                 });
             }
 
             fn user_event(&mut self, _event_loop: &ActiveEventLoop, event: ()) {
-                if let Some(ActiveWindow { events, .. }) = self.active_window.as_mut() {
-                    events.push(Event::UserEvent(event));
+                if let Some(active_window) = self.active_window.as_mut() {
+                    let is_background = active_window.is_background(self.data.background_unfocused);
+
+                    self.data.buffer_event(
+                        &mut active_window.events,
+                        Event::UserEvent(event),
+                        is_background,
+                    );
                 }
             }
 
@@ -257,31 +366,101 @@ impl Window {
                 window_id: WindowId,
                 event: WindowEvent,
             ) {
+                let mut is_device_lost = false;
+
                 if let Some(active_window) = self.active_window.as_mut() {
+                    let was_background =
+                        active_window.is_background(self.data.background_unfocused);
+
                     match &event {
                         WindowEvent::CloseRequested => {
                             info!("close requested");
 
                             event_loop.exit();
                         }
+                        WindowEvent::RedrawRequested
+                            if self
+                                .data
+                                .background_wait(
+                                    was_background,
+                                    active_window.next_frame,
+                                    Instant::now(),
+                                )
+                                .is_some() => {}
                         WindowEvent::RedrawRequested => {
-                            let draw = active_window.draw(&self.device, &mut self.draw_fn);
+                            // Background frames are not drawn until due, so this never sleeps while
+                            // the window is in the background
+                            if let Some(frame_interval) = self.data.frame_interval(was_background) {
+                                active_window.wait_for_next_frame(frame_interval);
+                            }
+
+                            let draw =
+                                active_window.draw(event_loop, &self.device, &mut self.draw_fn);
 
                             profiling::finish_frame!();
 
-                            if !draw.unwrap() {
-                                event_loop.exit();
+                            match draw {
+                                Ok(true) => (),
+                                Ok(false) => event_loop.exit(),
+                                Err(DisplayError::DeviceLost)
+                                    if self.data.device_lost_fn.is_some() =>
+                                {
+                                    warn!("device lost");
+
+                                    is_device_lost = true;
+                                }
+                                Err(err) => {
+                                    warn!("unable to draw: {err}");
+
+                                    self.error = Some(err.into());
+                                    event_loop.exit();
+                                }
                             }
                         }
+                        WindowEvent::Focused(is_focused) => {
+                            active_window.is_focused = *is_focused;
+                        }
+                        WindowEvent::Occluded(is_occluded) => {
+                            active_window.is_occluded = *is_occluded;
+                        }
                         WindowEvent::Resized(size) => {
                             active_window.display_resize = Some((size.width, size.height));
+                            active_window.is_minimized = size.width == 0 || size.height == 0;
                         }
                         _ => (),
                     }
 
-                    active_window
-                        .events
-                        .push(Event::WindowEvent { window_id, event });
+                    let is_background = active_window.is_background(self.data.background_unfocused);
+
+                    if is_background != was_background {
+                        info!(
+                            "window moved to the {}",
+                            if is_background {
+                                "background"
+                            } else {
+                                "foreground"
+                            }
+                        );
+
+                        if let Some(f) = self.data.background_fn.as_mut() {
+                            f(is_background);
+                        }
+                    }
+
+                    self.data.buffer_event(
+                        &mut active_window.events,
+                        Event::WindowEvent { window_id, event },
+                        is_background,
+                    );
+                }
+
+                if is_device_lost {
+                    if let Err(err) = self.recreate_device() {
+                        warn!("unable to recreate device: {err}");
+
+                        self.error = Some(err.into());
+                        event_loop.exit();
+                    }
                 }
             }
         }
@@ -291,12 +470,17 @@ impl Window {
             display_pool: HashPool,
             display_resize: Option<(u32, u32)>,
             events: Vec<Event<()>>,
+            is_focused: bool,
+            is_minimized: bool,
+            is_occluded: bool,
+            next_frame: Instant,
             window: winit::window::Window,
         }
 
         impl ActiveWindow {
             fn draw(
                 &mut self,
+                event_loop: &ActiveEventLoop,
                 device: &Arc<Device>,
                 mut f: impl FnMut(FrameContext),
             ) -> Result<bool, DisplayError> {
@@ -318,6 +502,7 @@ impl Window {
 
                     f(FrameContext {
                         device,
+                        event_loop,
                         events: &self.events,
                         height: swapchain_info.height,
                         render_graph: &mut render_graph,
@@ -349,6 +534,31 @@ impl Window {
 
                 Ok(true)
             }
+
+            fn is_background(&self, background_unfocused: bool) -> bool {
+                self.is_minimized || self.is_occluded || (background_unfocused && !self.is_focused)
+            }
+
+            /// Sleeps, and then spins for the final stretch, until the next frame is due.
+            fn wait_for_next_frame(&mut self, frame_interval: Duration) {
+                const SPIN_DURATION: Duration = Duration::from_millis(1);
+
+                let now = Instant::now();
+                if let Some(sleep_duration) = self
+                    .next_frame
+                    .checked_duration_since(now)
+                    .and_then(|duration| duration.checked_sub(SPIN_DURATION))
+                {
+                    sleep(sleep_duration);
+                }
+
+                while Instant::now() < self.next_frame {
+                    spin_loop();
+                }
+
+                // Don't try to catch up on frames which were missed
+                self.next_frame = (self.next_frame + frame_interval).max(Instant::now());
+            }
         }
 
         let mut app = Application {
@@ -388,8 +598,13 @@ impl AsRef<EventLoop<()>> for Window {
 
 pub struct WindowBuilder {
     attributes: WindowAttributes,
+    background_fn: Option<Box<dyn FnMut(bool)>>,
+    background_mode: BackgroundMode,
+    background_unfocused: bool,
     cmd_buf_count: usize,
     device_info: DeviceInfo,
+    device_lost_fn: Option<Box<dyn FnMut(&Arc<Device>)>>,
+    frame_interval: Option<Duration>,
     image_count: Option<u32>,
     surface_format_fn: Option<Box<dyn Fn(&[vk::SurfaceFormatKHR]) -> vk::SurfaceFormatKHR>>,
     v_sync: Option<bool>,
@@ -404,7 +619,13 @@ impl WindowBuilder {
         Ok(Window {
             data: WindowData {
                 attributes: self.attributes,
+                background_fn: self.background_fn,
+                background_mode: self.background_mode,
+                background_unfocused: self.background_unfocused,
                 cmd_buf_count: self.cmd_buf_count,
+                debug: self.device_info.debug,
+                device_lost_fn: self.device_lost_fn,
+                frame_interval: self.frame_interval,
                 image_count: self.image_count,
                 surface_format_fn: self.surface_format_fn,
                 v_sync: self.v_sync,
@@ -415,6 +636,36 @@ impl WindowBuilder {
         })
     }
 
+    /// A function which is called with `true` when the window moves to the background and `false`
+    /// when it returns to the foreground.
+    ///
+    /// See [`BackgroundMode`] for details.
+    pub fn background_fn<F>(mut self, f: F) -> Self
+    where
+        F: 'static + FnMut(bool),
+    {
+        self.background_fn = Some(Box::new(f));
+        self
+    }
+
+    /// Specifies how frames are drawn while the window is in the background.
+    ///
+    /// The default value is [`BackgroundMode::Continue`].
+    pub fn background_mode(mut self, mode: BackgroundMode) -> Self {
+        self.background_mode = mode;
+        self
+    }
+
+    /// When `true` specifies that the window is also in the background while it does not have
+    /// keyboard focus.
+    ///
+    /// The default value is `false`, meaning only minimized or fully hidden windows are in the
+    /// background.
+    pub fn background_when_unfocused(mut self, enabled: bool) -> Self {
+        self.background_unfocused = enabled;
+        self
+    }
+
     /// Specifies the number of in-flight command buffers, which should be greater
     /// than or equal to the desired swapchain image count.
     ///
@@ -442,6 +693,20 @@ impl WindowBuilder {
         self
     }
 
+    /// A function which is called with the new device after the device has been lost and
+    /// recreated while running the window.
+    ///
+    /// The program should load its resources again using the new device, which is also provided
+    /// to frames drawn afterwards. The device previously stored in [`Window::device`] must no
+    /// longer be used.
+    pub fn device_lost_fn<F>(mut self, f: F) -> Self
+    where
+        F: 'static + FnMut(&Arc<Device>),
+    {
+        self.device_lost_fn = Some(Box::new(f));
+        self
+    }
+
     /// A function to select the desired swapchain surface image format.
     ///
     /// By default linear color space will be selected unless it is not available.
@@ -461,6 +726,15 @@ impl WindowBuilder {
         self
     }
 
+    /// Limits the rate at which frames are drawn, independent of `v_sync`.
+    ///
+    /// A value of `None` (the default) draws frames as fast as the swapchain allows. Rates which
+    /// are not positive, or are too small to represent as a frame interval, are also unlimited.
+    pub fn frame_rate_limit(mut self, fps: impl Into<Option<f32>>) -> Self {
+        self.frame_interval = fps.into().and_then(frame_interval);
+        self
+    }
+
     /// Sets up fullscreen mode. In addition, decorations are set to `false` and maximized is set to
     /// `true`.
     ///
@@ -509,8 +783,13 @@ impl fmt::Debug for WindowBuilder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("WindowBuilder")
             .field("attributes", &self.attributes)
+            .field("background_fn", &self.background_fn.as_ref().map(|_| ()))
+            .field("background_mode", &self.background_mode)
+            .field("background_unfocused", &self.background_unfocused)
             .field("cmd_buffer_count", &self.cmd_buf_count)
             .field("device_info", &self.device_info)
+            .field("device_lost_fn", &self.device_lost_fn.as_ref().map(|_| ()))
+            .field("frame_interval", &self.frame_interval)
             .field("image_count", &self.image_count)
             .field(
                 "surface_format_fn",
@@ -526,8 +805,13 @@ impl Default for WindowBuilder {
     fn default() -> Self {
         Self {
             attributes: Default::default(),
+            background_fn: None,
+            background_mode: Default::default(),
+            background_unfocused: false,
             cmd_buf_count: 5,
             device_info: Default::default(),
+            device_lost_fn: None,
+            frame_interval: None,
             image_count: None,
             surface_format_fn: None,
             v_sync: None,
@@ -538,13 +822,94 @@ impl Default for WindowBuilder {
 
 struct WindowData {
     attributes: WindowAttributes,
+    background_fn: Option<Box<dyn FnMut(bool)>>,
+    background_mode: BackgroundMode,
+    background_unfocused: bool,
     cmd_buf_count: usize,
+    debug: bool,
+    device_lost_fn: Option<Box<dyn FnMut(&Arc<Device>)>>,
+    frame_interval: Option<Duration>,
     image_count: Option<u32>,
     surface_format_fn: Option<Box<dyn Fn(&[vk::SurfaceFormatKHR]) -> vk::SurfaceFormatKHR>>,
     v_sync: Option<bool>,
     window_mode_override: Option<Option<FullscreenMode>>,
 }
 
+impl WindowData {
+    /// The number of events which may be buffered between frames.
+    const MAX_EVENTS: usize = 1_024;
+
+    /// Returns the control flow the event loop should wait with instead of drawing a frame, or
+    /// `None` if a frame should be drawn now.
+    ///
+    /// Background frames are scheduled using the event loop instead of by sleeping, so that events
+    /// which restore or close the window are still handled while waiting.
+    fn background_wait(
+        &self,
+        is_background: bool,
+        next_frame: Instant,
+        now: Instant,
+    ) -> Option<ControlFlow> {
+        if !is_background {
+            return None;
+        }
+
+        match self.background_mode {
+            BackgroundMode::Pause => Some(ControlFlow::Wait),
+            _ => (self.frame_interval(true).is_some() && now < next_frame)
+                .then_some(ControlFlow::WaitUntil(next_frame)),
+        }
+    }
+
+    /// Buffers an event for the next frame.
+    ///
+    /// Device events are discarded while frames are paused in the background, and the oldest half
+    /// of the buffered events are discarded once [`Self::MAX_EVENTS`] events are buffered, so that
+    /// stale events do not pile up while no frames are drawn.
+    fn buffer_event(&self, events: &mut Vec<Event<()>>, event: Event<()>, is_background: bool) {
+        if is_background
+            && self.background_mode == BackgroundMode::Pause
+            && matches!(event, Event::DeviceEvent { .. })
+        {
+            return;
+        }
+
+        if events.len() >= Self::MAX_EVENTS {
+            events.drain(..Self::MAX_EVENTS / 2);
+        }
+
+        events.push(event);
+    }
+
+    /// Returns the minimum time between frames, if frames are limited.
+    fn frame_interval(&self, is_background: bool) -> Option<Duration> {
+        match self.background_mode {
+            BackgroundMode::Throttle(fps) if is_background => {
+                let Some(throttle_interval) = frame_interval(fps) else {
+                    return self.frame_interval;
+                };
+
+                Some(
+                    self.frame_interval
+                        .map_or(throttle_interval, |frame_interval| {
+                            frame_interval.max(throttle_interval)
+                        }),
+                )
+            }
+            _ => self.frame_interval,
+        }
+    }
+}
+
+/// Returns the time between frames drawn at `fps` frames per second, if it is a usable rate.
+fn frame_interval(fps: f32) -> Option<Duration> {
+    if fps > 0.0 {
+        Duration::try_from_secs_f32(1.0 / fps).ok()
+    } else {
+        None
+    }
+}
+
 #[derive(Debug)]
 pub enum WindowError {
     Driver(DriverError),
@@ -569,6 +934,15 @@ impl fmt::Display for WindowError {
     }
 }
 
+impl From<DisplayError> for WindowError {
+    fn from(err: DisplayError) -> Self {
+        Self::Driver(match err {
+            DisplayError::DeviceLost => DriverError::DeviceLost,
+            DisplayError::Driver(err) => err,
+        })
+    }
+}
+
 impl From<DriverError> for WindowError {
     fn from(err: DriverError) -> Self {
         Self::Driver(err)
@@ -580,3 +954,138 @@ impl From<EventLoopError> for WindowError {
         Self::EventLoop(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window_data(background_mode: BackgroundMode, fps: Option<f32>) -> WindowData {
+        WindowData {
+            attributes: Default::default(),
+            background_fn: None,
+            background_mode,
+            background_unfocused: false,
+            cmd_buf_count: 0,
+            debug: false,
+            device_lost_fn: None,
+            frame_interval: fps.and_then(frame_interval),
+            image_count: None,
+            surface_format_fn: None,
+            v_sync: None,
+            window_mode_override: None,
+        }
+    }
+
+    #[test]
+    pub fn window_data_background_wait() {
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+
+        let data = window_data(BackgroundMode::Continue, None);
+
+        assert_eq!(data.background_wait(true, later, now), None);
+
+        let data = window_data(BackgroundMode::Pause, Some(8.0));
+
+        assert_eq!(data.background_wait(false, later, now), None);
+        assert_eq!(
+            data.background_wait(true, now, now),
+            Some(ControlFlow::Wait)
+        );
+
+        let data = window_data(BackgroundMode::Throttle(1.0), None);
+
+        assert_eq!(data.background_wait(false, later, now), None);
+        assert_eq!(data.background_wait(true, now, now), None);
+        assert_eq!(
+            data.background_wait(true, later, now),
+            Some(ControlFlow::WaitUntil(later))
+        );
+    }
+
+    #[test]
+    pub fn window_data_buffer_event() {
+        let device_event = || Event::DeviceEvent {
+            // Safety: the identifier is never used to query a device
+            device_id: unsafe { DeviceId::dummy() },
+            event: DeviceEvent::Added,
+        };
+        let mut events = vec![];
+
+        let data = window_data(BackgroundMode::Pause, None);
+
+        data.buffer_event(&mut events, device_event(), true);
+        data.buffer_event(&mut events, Event::UserEvent(()), true);
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Event::UserEvent(())));
+
+        data.buffer_event(&mut events, device_event(), false);
+
+        assert_eq!(events.len(), 2);
+
+        let data = window_data(BackgroundMode::Throttle(1.0), None);
+
+        data.buffer_event(&mut events, device_event(), true);
+
+        assert_eq!(events.len(), 3);
+
+        for _ in 0..WindowData::MAX_EVENTS * 4 {
+            data.buffer_event(&mut events, device_event(), true);
+        }
+
+        assert!(events.len() <= WindowData::MAX_EVENTS);
+        assert!(matches!(events.last(), Some(Event::DeviceEvent { .. })));
+    }
+
+    #[test]
+    pub fn window_data_frame_interval() {
+        let ms = Duration::from_millis;
+
+        let data = window_data(BackgroundMode::Continue, None);
+
+        assert_eq!(data.frame_interval(false), None);
+        assert_eq!(data.frame_interval(true), None);
+
+        let data = window_data(BackgroundMode::Continue, Some(8.0));
+
+        assert_eq!(data.frame_interval(false), Some(ms(125)));
+        assert_eq!(data.frame_interval(true), Some(ms(125)));
+
+        // The slower of the throttle and frame rate limit applies in the background
+        let data = window_data(BackgroundMode::Throttle(2.0), Some(8.0));
+
+        assert_eq!(data.frame_interval(false), Some(ms(125)));
+        assert_eq!(data.frame_interval(true), Some(ms(500)));
+
+        let data = window_data(BackgroundMode::Throttle(16.0), Some(8.0));
+
+        assert_eq!(data.frame_interval(true), Some(ms(125)));
+
+        for fps in [0.0, -1.0, f32::NAN, f32::MIN_POSITIVE / 2.0] {
+            let data = window_data(BackgroundMode::Throttle(fps), None);
+
+            assert_eq!(data.frame_interval(true), None);
+
+            let data = window_data(BackgroundMode::Throttle(fps), Some(8.0));
+
+            assert_eq!(data.frame_interval(true), Some(ms(125)));
+        }
+    }
+
+    #[test]
+    pub fn window_builder_frame_rate_limit() {
+        let limit = |fps| {
+            WindowBuilder::default()
+                .frame_rate_limit(fps)
+                .frame_interval
+        };
+
+        assert_eq!(limit(Some(4.0)), Some(Duration::from_millis(250)));
+        assert_eq!(limit(None), None);
+        assert_eq!(limit(Some(0.0)), None);
+        assert_eq!(limit(Some(-60.0)), None);
+        assert_eq!(limit(Some(f32::NAN)), None);
+        assert_eq!(limit(Some(f32::MIN_POSITIVE / 2.0)), None);
+    }
+}
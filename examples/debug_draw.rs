@@ -0,0 +1,58 @@
+mod profile_with_puffin;
+
+use {
+    clap::Parser,
+    glam::{Mat4, Quat, Vec3},
+    screen_13_fx::*,
+    screen_13_window::WindowBuilder,
+    std::time::Instant,
+};
+
+// Immediate-mode debug lines: a rotating set of axes inside a grid of bounding boxes.
+fn main() -> anyhow::Result<()> {
+    pretty_env_logger::init();
+    profile_with_puffin::init();
+
+    let args = Args::parse();
+    let window = WindowBuilder::default().debug(args.debug).build()?;
+    let mut debug_draw = DebugDraw::new(&window.device)?;
+    let started = Instant::now();
+
+    window.run(|frame| {
+        let elapsed = started.elapsed().as_secs_f32();
+        let aspect_ratio = frame.width as f32 / frame.height.max(1) as f32;
+        let view_proj = Mat4::perspective_rh(45f32.to_radians(), aspect_ratio, 0.1, 100.0)
+            * Mat4::look_at_rh(Vec3::new(4.0, 3.0, 6.0), Vec3::ZERO, Vec3::Y);
+
+        for x in -2..=2 {
+            for z in -2..=2 {
+                let min = Vec3::new(x as f32, -0.5, z as f32) - 0.4;
+                let max = min + 0.8;
+
+                debug_draw.aabb(min.to_array(), max.to_array(), [0.5, 0.5, 0.5, 1.0]);
+            }
+        }
+
+        debug_draw.axes(
+            Mat4::from_rotation_translation(Quat::from_rotation_y(elapsed), Vec3::Y)
+                .to_cols_array(),
+            1.5,
+        );
+
+        frame.render_graph.clear_color_image(frame.swapchain_image);
+        debug_draw.draw(
+            frame.render_graph,
+            frame.swapchain_image,
+            view_proj.to_cols_array(),
+        );
+    })?;
+
+    Ok(())
+}
+
+#[derive(Parser)]
+struct Args {
+    /// Enable Vulkan SDK validation layers
+    #[arg(long)]
+    debug: bool,
+}
@@ -230,7 +230,11 @@ impl Display {
                 .map_err(|err| {
                     warn!("unable to submit display command buffer: {err}");
 
-                    DriverError::InvalidData
+                    if err == vk::Result::ERROR_DEVICE_LOST {
+                        DriverError::DeviceLost
+                    } else {
+                        DriverError::InvalidData
+                    }
                 })?
         }
 
@@ -338,7 +342,10 @@ impl From<()> for DisplayError {
 
 impl From<DriverError> for DisplayError {
     fn from(err: DriverError) -> Self {
-        Self::Driver(err)
+        match err {
+            DriverError::DeviceLost => Self::DeviceLost,
+            err => Self::Driver(err),
+        }
     }
 }
 
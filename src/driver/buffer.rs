@@ -1,7 +1,10 @@
 //! Buffer resource types
 
 use {
-    super::{DriverError, device::Device},
+    super::{
+        DriverError,
+        device::{Device, MemoryCategory},
+    },
     ash::vk,
     derive_builder::{Builder, UninitializedFieldError},
     gpu_allocator::{
@@ -64,6 +67,9 @@ pub struct Buffer {
     pub info: BufferInfo,
 
     /// A name for debugging purposes.
+    ///
+    /// Assigning this field directly only affects _Screen 13_ output such as logging and graph
+    /// dumps; use [`Buffer::set_name`] to also name the Vulkan object.
     pub name: Option<String>,
 }
 
@@ -130,7 +136,7 @@ impl Buffer {
 
             allocator
                 .allocate(&AllocationCreateDesc {
-                    name: "buffer",
+                    name: Self::memory_category(&info).name(),
                     requirements,
                     location: memory_location,
                     linear: true, // Buffers are always linear
@@ -431,6 +437,41 @@ impl Buffer {
 
         &mut this.allocation.mapped_slice_mut().unwrap()[0..this.info.size as usize]
     }
+
+    /// Sets the debugging name of this buffer.
+    ///
+    /// The Vulkan buffer is also named when debugging or the `debug-names` feature is enabled, so
+    /// the name appears in validation messages and graphics debuggers.
+    pub fn set_name(this: &mut Self, name: impl Into<String>) {
+        let name = name.into();
+
+        Device::set_debug_name(&this.device, this.buffer, &name);
+
+        this.name = Some(name);
+    }
+
+    /// Describes the allocation in device memory reports.
+    fn memory_category(info: &BufferInfo) -> MemoryCategory {
+        let transfer = vk::BufferUsageFlags::TRANSFER_DST | vk::BufferUsageFlags::TRANSFER_SRC;
+
+        if info
+            .usage
+            .contains(vk::BufferUsageFlags::ACCELERATION_STRUCTURE_STORAGE_KHR)
+        {
+            MemoryCategory::AccelerationStructure
+        } else if info
+            .usage
+            .intersects(vk::BufferUsageFlags::INDEX_BUFFER | vk::BufferUsageFlags::VERTEX_BUFFER)
+        {
+            MemoryCategory::GeometryBuffer
+        } else if info.mappable && transfer.contains(info.usage) && !info.usage.is_empty() {
+            MemoryCategory::StagingBuffer
+        } else if info.mappable {
+            MemoryCategory::HostBuffer
+        } else {
+            MemoryCategory::Buffer
+        }
+    }
 }
 
 impl Debug for Buffer {
@@ -1333,6 +1374,41 @@ mod tests {
         Builder::default().build();
     }
 
+    #[test]
+    pub fn buffer_memory_category() {
+        use {MemoryCategory as C, vk::BufferUsageFlags as U};
+
+        let category = |info| Buffer::memory_category(&info);
+
+        assert_eq!(
+            category(Info::device_mem(1, U::ACCELERATION_STRUCTURE_STORAGE_KHR)),
+            C::AccelerationStructure
+        );
+        assert_eq!(category(Info::device_mem(1, U::STORAGE_BUFFER)), C::Buffer);
+        assert_eq!(
+            category(Info::device_mem(1, U::VERTEX_BUFFER | U::TRANSFER_DST)),
+            C::GeometryBuffer
+        );
+        assert_eq!(
+            category(Info::host_mem(1, U::INDEX_BUFFER)),
+            C::GeometryBuffer
+        );
+        assert_eq!(
+            category(Info::host_mem(1, U::UNIFORM_BUFFER)),
+            C::HostBuffer
+        );
+        assert_eq!(category(Info::host_mem(1, U::empty())), C::HostBuffer);
+        assert_eq!(
+            category(Info::host_mem(1, U::TRANSFER_SRC)),
+            C::StagingBuffer
+        );
+        assert_eq!(
+            category(Info::host_mem(1, U::TRANSFER_DST | U::TRANSFER_SRC)),
+            C::StagingBuffer
+        );
+        assert_eq!(category(Info::device_mem(1, U::TRANSFER_SRC)), C::Buffer);
+    }
+
     fn buffer_subresource_range(
         Range { start, end }: Range<vk::DeviceSize>,
     ) -> BufferSubresourceRange {
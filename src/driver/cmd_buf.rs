@@ -93,7 +93,7 @@ impl CommandBuffer {
             Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
                 error!("Device lost");
 
-                Err(DriverError::InvalidData)
+                Err(DriverError::DeviceLost)
             }
             Err(err) => {
                 // VK_SUCCESS and VK_NOT_READY handled by get_fence_status in ash
@@ -170,7 +170,12 @@ impl ComputePipeline {
 
     /// Sets the debugging name assigned to this pipeline.
     pub fn with_name(mut this: Self, name: impl Into<String>) -> Self {
-        this.name = Some(name.into());
+        let name = name.into();
+
+        Device::set_debug_name(&this.device, this.pipeline, &name);
+        Device::set_debug_name(&this.device, this.layout, &name);
+
+        this.name = Some(name);
         this
     }
 }
@@ -9,12 +9,13 @@ use {
         AllocatorDebugSettings,
         vulkan::{Allocator, AllocatorCreateDesc},
     },
-    log::{error, info, trace, warn},
+    log::{Level, error, info, trace, warn},
     raw_window_handle::HasDisplayHandle,
     std::{
         cmp::Ordering,
+        collections::BTreeMap,
         ffi::{CStr, CString},
-        fmt::{Debug, Formatter},
+        fmt::{Debug, Display, Formatter},
         iter::{empty, repeat_n},
         mem::{ManuallyDrop, forget},
         ops::Deref,
@@ -29,6 +30,9 @@ use parking_lot::Mutex;
 #[cfg(not(feature = "parking_lot"))]
 use std::sync::Mutex;
 
+/// Function type for validation layer messages, which receives the `log` level of each message.
+pub type DebugMessageFn = dyn Fn(Level, &str) + Send + Sync;
+
 /// Function type for selection of physical devices.
 pub type SelectPhysicalDeviceFn = dyn FnOnce(&[PhysicalDevice]) -> usize;
 
@@ -341,9 +345,12 @@ impl Device {
     pub fn create_headless(info: impl Into<DeviceInfo>) -> Result<Self, DriverError> {
         let DeviceInfo {
             debug,
+            debug_callback,
             select_physical_device,
+            sync_validation,
         } = info.into();
-        let instance = Instance::create(debug, empty())?;
+        let instance =
+            Instance::create_with_validation(debug, sync_validation, debug_callback, empty())?;
 
         Self::create(instance, select_physical_device, false)
     }
@@ -356,7 +363,9 @@ impl Device {
     ) -> Result<Self, DriverError> {
         let DeviceInfo {
             debug,
+            debug_callback,
             select_physical_device,
+            sync_validation,
         } = info.into();
         let display_handle = display_handle.display_handle().map_err(|err| {
             warn!("{err}");
@@ -371,7 +380,12 @@ impl Device {
             })?
             .iter()
             .map(|ext| unsafe { CStr::from_ptr(*ext as *const _) });
-        let instance = Instance::create(debug, required_extensions)?;
+        let instance = Instance::create_with_validation(
+            debug,
+            sync_validation,
+            debug_callback,
+            required_extensions,
+        )?;
 
         Self::create(instance, select_physical_device, true)
     }
@@ -490,11 +504,8 @@ impl Device {
 
         let push_descriptor_ext = ash::khr::push_descriptor::Device::new(&instance, &device);
 
-        let debug_utils_fn = if debug {
-            Some(ext::debug_utils::Device::new(&instance, &device))
-        } else {
-            None
-        };
+        let debug_utils_fn = Instance::is_debug_utils(&instance)
+            .then(|| ext::debug_utils::Device::new(&instance, &device));
 
         let pipeline_cache =
             unsafe { device.create_pipeline_cache(&vk::PipelineCacheCreateInfo::default(), None) }
@@ -569,6 +580,63 @@ impl Device {
         &this.instance
     }
 
+    /// Assigns a name to a Vulkan object for use by debuggers and validation messages.
+    ///
+    /// Has no effect unless debugging or the `debug-names` feature is enabled and the driver
+    /// provides `VK_EXT_debug_utils`.
+    pub(crate) fn set_debug_name(this: &Self, handle: impl vk::Handle, name: &str) {
+        let Some(debug_utils_fn) = &this.debug_utils_fn else {
+            return;
+        };
+
+        let Ok(name) = CString::new(name) else {
+            warn!("invalid debug name: {name}");
+
+            return;
+        };
+
+        if let Err(err) = unsafe {
+            debug_utils_fn.set_debug_utils_object_name(
+                &vk::DebugUtilsObjectNameInfoEXT::default()
+                    .object_handle(handle)
+                    .object_name(&name),
+            )
+        } {
+            warn!("unable to set debug name: {err}");
+        }
+    }
+
+    /// Returns a summary of the device memory currently allocated for the buffers, images and
+    /// acceleration structures created using this device, including pooled resources.
+    #[profiling::function]
+    pub fn memory_report(this: &Self) -> MemoryReport {
+        let allocator = this.allocator.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let allocator = allocator.unwrap();
+
+        let report = allocator.generate_report();
+        let mut categories = BTreeMap::<_, MemoryUsage>::new();
+
+        for allocation in &report.allocations {
+            let Some(category) = MemoryCategory::from_name(&allocation.name) else {
+                continue;
+            };
+
+            let usage = categories.entry(category).or_default();
+            usage.allocated_bytes += allocation.size;
+            usage.allocation_count += 1;
+        }
+
+        MemoryReport {
+            allocated_bytes: report.total_allocated_bytes,
+            allocation_count: report.allocations.len(),
+            block_count: report.blocks.len(),
+            capacity_bytes: report.total_capacity_bytes,
+            categories,
+        }
+    }
+
     pub(crate) fn pipeline_cache(this: &Self) -> vk::PipelineCache {
         this.pipeline_cache
     }
@@ -588,7 +656,7 @@ impl Device {
                 Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
                     error!("Device lost");
 
-                    return Err(DriverError::InvalidData);
+                    return Err(DriverError::DeviceLost);
                 }
                 Err(err) if err == vk::Result::TIMEOUT => {
                     trace!("waiting...");
@@ -603,7 +671,7 @@ impl Device {
                 Err(err) if err == vk::Result::ERROR_DEVICE_LOST => {
                     error!("Device lost");
 
-                    return Err(DriverError::InvalidData);
+                    return Err(DriverError::DeviceLost);
                 }
                 _ => return Err(DriverError::OutOfMemory),
             }
@@ -708,10 +776,31 @@ pub struct DeviceInfo {
     #[builder(default)]
     pub debug: bool,
 
+    /// Callback function which receives each validation layer message after it has been logged.
+    ///
+    /// Messages are logged at the level matching their severity: errors as `error`, warnings as
+    /// `warn`, information as `debug` and verbose messages as `trace`. The callback may be used to
+    /// fail tests when validation errors occur.
+    ///
+    /// Requires [`DeviceInfo::debug`].
+    #[builder(default, setter(strip_option))]
+    pub debug_callback: Option<Box<DebugMessageFn>>,
+
     /// Callback function used to select a [`PhysicalDevice`] from the available devices. The
     /// callback must return the index of the selected device.
     #[builder(default = "Box::new(DeviceInfo::discrete_gpu)")]
     pub select_physical_device: Box<SelectPhysicalDeviceFn>,
+
+    /// Enables synchronization validation, which reports missing or incorrect barriers between
+    /// commands.
+    ///
+    /// Requires [`DeviceInfo::debug`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// **macOS:** Has no effect.
+    #[builder(default)]
+    pub sync_validation: bool,
 }
 
 impl DeviceInfo {
@@ -810,7 +899,9 @@ impl DeviceInfo {
     pub fn to_builder(self) -> DeviceInfoBuilder {
         DeviceInfoBuilder {
             debug: Some(self.debug),
+            debug_callback: Some(self.debug_callback),
             select_physical_device: Some(self.select_physical_device),
+            sync_validation: Some(self.sync_validation),
         }
     }
 }
@@ -819,7 +910,12 @@ impl Debug for DeviceInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DeviceInfo")
             .field("debug", &self.debug)
+            .field(
+                "debug_callback",
+                &self.debug_callback.as_ref().map(|_| "fn"),
+            )
             .field("select_physical_device", &"fn")
+            .field("sync_validation", &self.sync_validation)
             .finish()
     }
 }
@@ -828,7 +924,9 @@ impl Default for DeviceInfo {
     fn default() -> Self {
         Self {
             debug: false,
+            debug_callback: None,
             select_physical_device: Box::new(DeviceInfo::discrete_gpu),
+            sync_validation: false,
         }
     }
 }
@@ -864,6 +962,141 @@ impl From<UninitializedFieldError> for DeviceInfoBuilderError {
     }
 }
 
+/// The kind of resource which device memory was allocated for.
+///
+/// Descriptor pools and other driver-managed objects do not use device memory allocated by
+/// _Screen 13_ and are not reported.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum MemoryCategory {
+    /// Buffers which store acceleration structures.
+    AccelerationStructure,
+
+    /// Device-local buffers which are not used for geometry.
+    Buffer,
+
+    /// Index and vertex buffers, such as model data.
+    GeometryBuffer,
+
+    /// Host-mappable buffers which are not used only for transfers, such as uniform data which is
+    /// updated each frame.
+    HostBuffer,
+
+    /// Images which are not used as render targets, storage or textures.
+    Image,
+
+    /// Color and depth/stencil attachment images.
+    RenderTarget,
+
+    /// Host-mappable buffers which are only used for transfers, such as uploads and readbacks.
+    StagingBuffer,
+
+    /// Storage images which are not render targets.
+    StorageImage,
+
+    /// Sampled images, such as bitmaps and textures.
+    Texture,
+}
+
+impl MemoryCategory {
+    const ALL: [Self; 9] = [
+        Self::AccelerationStructure,
+        Self::Buffer,
+        Self::GeometryBuffer,
+        Self::HostBuffer,
+        Self::Image,
+        Self::RenderTarget,
+        Self::StagingBuffer,
+        Self::StorageImage,
+        Self::Texture,
+    ];
+
+    /// Returns the name used for allocations of this category.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::AccelerationStructure => "acceleration structure",
+            Self::Buffer => "buffer",
+            Self::GeometryBuffer => "geometry buffer",
+            Self::HostBuffer => "host buffer",
+            Self::Image => "image",
+            Self::RenderTarget => "render target",
+            Self::StagingBuffer => "staging buffer",
+            Self::StorageImage => "storage image",
+            Self::Texture => "texture",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name() == name)
+    }
+}
+
+impl Display for MemoryCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Summary of device memory allocated by a [`Device`].
+///
+/// The [`Display`] implementation is suitable for logging, for example:
+/// `info!("{}", Device::memory_report(&device))`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MemoryReport {
+    /// Total size of all live allocations, in bytes.
+    pub allocated_bytes: vk::DeviceSize,
+
+    /// Number of live allocations.
+    pub allocation_count: usize,
+
+    /// Number of device memory blocks which back the allocations.
+    pub block_count: usize,
+
+    /// Total size of all device memory blocks, in bytes.
+    pub capacity_bytes: vk::DeviceSize,
+
+    /// Live allocations grouped by the kind of resource they were allocated for.
+    pub categories: BTreeMap<MemoryCategory, MemoryUsage>,
+}
+
+impl Display for MemoryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations using {} K of {} K in {} blocks",
+            self.allocation_count,
+            self.allocated_bytes / 1024,
+            self.capacity_bytes / 1024,
+            self.block_count
+        )?;
+
+        for (category, usage) in &self.categories {
+            write!(
+                f,
+                "\n  {category}: {} allocations using {} K",
+                usage.allocation_count,
+                usage.allocated_bytes / 1024
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Device memory used by one category of a [`MemoryReport`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MemoryUsage {
+    /// Total size of the allocations, in bytes.
+    pub allocated_bytes: vk::DeviceSize,
+
+    /// Number of allocations.
+    pub allocation_count: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -880,4 +1113,46 @@ mod tests {
     pub fn device_info_builder() {
         Builder::default().build();
     }
+
+    #[test]
+    pub fn memory_category_name() {
+        for category in MemoryCategory::ALL {
+            assert_eq!(MemoryCategory::from_name(category.name()), Some(category));
+        }
+
+        assert_eq!(MemoryCategory::from_name("unknown"), None);
+    }
+
+    #[test]
+    pub fn memory_report_display() {
+        let report = MemoryReport {
+            allocated_bytes: 3 * 1024,
+            allocation_count: 3,
+            block_count: 1,
+            capacity_bytes: 64 * 1024,
+            categories: [
+                (
+                    MemoryCategory::Texture,
+                    MemoryUsage {
+                        allocated_bytes: 2 * 1024,
+                        allocation_count: 2,
+                    },
+                ),
+                (
+                    MemoryCategory::GeometryBuffer,
+                    MemoryUsage {
+                        allocated_bytes: 1024,
+                        allocation_count: 1,
+                    },
+                ),
+            ]
+            .into(),
+        };
+
+        assert_eq!(
+            report.to_string(),
+            "3 allocations using 3 K of 64 K in 1 blocks\n  geometry buffer: 1 allocations using 1 \
+            K\n  texture: 2 allocations using 2 K"
+        );
+    }
 }
@@ -592,7 +592,12 @@ impl GraphicPipeline {
 
     /// Sets the debugging name assigned to this pipeline.
     pub fn with_name(mut this: Self, name: impl Into<String>) -> Self {
-        this.name = Some(name.into());
+        let name = name.into();
+
+        // Pipeline objects are created per render pass; those are named as they are created
+        Device::set_debug_name(&this.device, this.layout, &name);
+
+        this.name = Some(name);
         this
     }
 }
@@ -1,7 +1,11 @@
 //! Image resource types
 
 use {
-    super::{DriverError, device::Device, format_aspect_mask},
+    super::{
+        DriverError,
+        device::{Device, MemoryCategory},
+        format_aspect_mask,
+    },
     ash::vk::{self, ImageCreateInfo},
     derive_builder::{Builder, UninitializedFieldError},
     gpu_allocator::{
@@ -104,6 +108,9 @@ pub struct Image {
     pub info: ImageInfo,
 
     /// A name for debugging purposes.
+    ///
+    /// Assigning this field directly only affects _Screen 13_ output such as logging and graph
+    /// dumps; use [`Image::set_name`] to also name the Vulkan object.
     pub name: Option<String>,
 }
 
@@ -168,7 +175,7 @@ impl Image {
 
             allocator
                 .allocate(&AllocationCreateDesc {
-                    name: "image",
+                    name: Self::memory_category(&info).name(),
                     requirements,
                     location: MemoryLocation::GpuOnly,
                     linear: false,
@@ -387,6 +394,33 @@ impl Image {
         }
     }
 
+    /// Describes the allocation in device memory reports.
+    fn memory_category(info: &ImageInfo) -> MemoryCategory {
+        if info.usage.intersects(
+            vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT,
+        ) {
+            MemoryCategory::RenderTarget
+        } else if info.usage.contains(vk::ImageUsageFlags::STORAGE) {
+            MemoryCategory::StorageImage
+        } else if info.usage.contains(vk::ImageUsageFlags::SAMPLED) {
+            MemoryCategory::Texture
+        } else {
+            MemoryCategory::Image
+        }
+    }
+
+    /// Sets the debugging name of this image.
+    ///
+    /// The Vulkan image is also named when debugging or the `debug-names` feature is enabled, so
+    /// the name appears in validation messages and graphics debuggers.
+    pub fn set_name(this: &mut Self, name: impl Into<String>) {
+        let name = name.into();
+
+        Device::set_debug_name(&this.device, this.image, &name);
+
+        this.name = Some(name);
+    }
+
     /// Creates a new image view for this image.
     #[profiling::function]
     pub fn view(this: &Self, info: ImageViewInfo) -> Result<vk::ImageView, DriverError> {
@@ -2219,6 +2253,29 @@ mod tests {
             .build();
     }
 
+    #[test]
+    pub fn image_memory_category() {
+        use {MemoryCategory as C, vk::ImageUsageFlags as U};
+
+        let category = |usage| {
+            Image::memory_category(&ImageInfo::image_2d(
+                1,
+                1,
+                vk::Format::R8G8B8A8_UNORM,
+                usage,
+            ))
+        };
+
+        assert_eq!(
+            category(U::COLOR_ATTACHMENT | U::SAMPLED | U::STORAGE),
+            C::RenderTarget
+        );
+        assert_eq!(category(U::DEPTH_STENCIL_ATTACHMENT), C::RenderTarget);
+        assert_eq!(category(U::SAMPLED | U::STORAGE), C::StorageImage);
+        assert_eq!(category(U::SAMPLED | U::TRANSFER_DST), C::Texture);
+        assert_eq!(category(U::TRANSFER_SRC), C::Image);
+    }
+
     fn image_subresource(
         fmt: vk::Format,
         array_layer_count: u32,
@@ -1,5 +1,5 @@
 use {
-    super::{DriverError, physical_device::PhysicalDevice},
+    super::{DriverError, device::DebugMessageFn, physical_device::PhysicalDevice},
     ash::{Entry, ext, vk},
     log::{debug, error, trace, warn},
     std::{
@@ -13,7 +13,7 @@ use {
 
 #[cfg(not(target_os = "macos"))]
 use {
-    log::{Level, Metadata, info, logger},
+    log::{Level, Metadata, info, log, logger},
     std::{
         env::var,
         ffi::c_void,
@@ -29,55 +29,30 @@ use {
 
 #[cfg(not(target_os = "macos"))]
 unsafe extern "system" fn vulkan_debug_callback(
-    _flags: vk::DebugReportFlagsEXT,
-    _obj_type: vk::DebugReportObjectTypeEXT,
-    _src_obj: u64,
-    _location: usize,
-    _msg_code: i32,
-    _layer_prefix: *const c_char,
-    message: *const c_char,
-    _user_data: *mut c_void,
-) -> u32 {
-    if panicking() {
+    message_severity: vk::DebugUtilsMessageSeverityFlagsEXT,
+    _message_types: vk::DebugUtilsMessageTypeFlagsEXT,
+    callback_data: *const vk::DebugUtilsMessengerCallbackDataEXT<'_>,
+    user_data: *mut c_void,
+) -> vk::Bool32 {
+    if panicking() || callback_data.is_null() {
         return vk::FALSE;
     }
 
-    assert!(!message.is_null());
-
-    let mut found_null = false;
-    for i in 0..u16::MAX as _ {
-        if unsafe { *message.add(i) } == 0 {
-            found_null = true;
-            break;
+    let callback_data = unsafe { &*callback_data };
+    let message = unsafe { callback_data.message_as_c_str() }
+        .map(CStr::to_string_lossy)
+        .unwrap_or_default();
+    let message_id = unsafe { callback_data.message_id_name_as_c_str() }
+        .map(CStr::to_string_lossy)
+        .unwrap_or_default();
+    let level = debug_message_level(message_severity, &message_id);
+
+    if level == Level::Error {
+        if !message_id.is_empty() {
+            info!("{message_id}");
         }
-    }
-
-    assert!(found_null);
-
-    let message = unsafe { CStr::from_ptr(message) }.to_str().unwrap();
-
-    if message.starts_with("Validation Warning: [ UNASSIGNED-BestPractices-pipeline-stage-flags ]")
-    {
-        // vk_sync uses vk::PipelineStageFlags::ALL_COMMANDS with AccessType::NOTHING and others
-        warn!("{}", message);
-    } else {
-        let prefix = "Validation Error: [ ";
-
-        let (vuid, message) = if message.starts_with(prefix) {
-            let (vuid, message) = message
-                .trim_start_matches(prefix)
-                .split_once(" ]")
-                .unwrap_or_default();
-            let message = message.split(" | ").nth(2).unwrap_or(message);
-
-            (Some(vuid.trim()), message)
-        } else {
-            (None, message)
-        };
 
-        if let Some(vuid) = vuid {
-            info!("{vuid}");
-        }
+        let message = message.split(" | ").nth(2).unwrap_or(message.as_ref());
 
         error!("🆘 {message}");
 
@@ -106,20 +81,46 @@ unsafe extern "system" fn vulkan_debug_callback(
         // logger().flush();
 
         // park();
+    } else {
+        log!(level, "{message}");
+    }
+
+    if !user_data.is_null() {
+        let debug_message_fn = unsafe { &*(user_data as *const Box<DebugMessageFn>) };
+        debug_message_fn(level, message.as_ref());
     }
 
     vk::FALSE
 }
 
+/// Maps the severity of a validation layer message onto a `log` level.
+#[cfg(not(target_os = "macos"))]
+fn debug_message_level(severity: vk::DebugUtilsMessageSeverityFlagsEXT, message_id: &str) -> Level {
+    if message_id == "UNASSIGNED-BestPractices-pipeline-stage-flags" {
+        // vk_sync uses vk::PipelineStageFlags::ALL_COMMANDS with AccessType::NOTHING and others
+        return Level::Warn;
+    }
+
+    if severity.contains(vk::DebugUtilsMessageSeverityFlagsEXT::ERROR) {
+        Level::Error
+    } else if severity.contains(vk::DebugUtilsMessageSeverityFlagsEXT::WARNING) {
+        Level::Warn
+    } else if severity.contains(vk::DebugUtilsMessageSeverityFlagsEXT::INFO) {
+        Level::Debug
+    } else {
+        Level::Trace
+    }
+}
+
 /// There is no global state in Vulkan and all per-application state is stored in a VkInstance
 /// object.
 ///
 /// Creating an Instance initializes the Vulkan library and allows the application to pass
 /// information about itself to the implementation.
 pub struct Instance {
-    _debug_callback: Option<vk::DebugReportCallbackEXT>,
-    #[allow(deprecated)] // TODO: Remove? Look into this....
-    _debug_loader: Option<ext::debug_report::Instance>,
+    _debug_message_fn: Option<Box<Box<DebugMessageFn>>>,
+    debug: bool,
+    debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
     debug_utils: Option<ext::debug_utils::Instance>,
     entry: Entry,
     instance: ash::Instance,
@@ -132,6 +133,22 @@ impl Instance {
     pub fn create<'a>(
         debug: bool,
         required_extensions: impl Iterator<Item = &'a CStr>,
+    ) -> Result<Self, DriverError> {
+        Self::create_with_validation(debug, false, None, required_extensions)
+    }
+
+    /// Creates a new Vulkan instance with optional synchronization validation and a callback
+    /// function which receives each validation layer message after it has been logged.
+    ///
+    /// Validation layer messages are logged at the [`log::Level`] matching their severity.
+    ///
+    /// See [`DeviceInfo`](super::device::DeviceInfo) for details.
+    #[profiling::function]
+    pub fn create_with_validation<'a>(
+        debug: bool,
+        sync_validation: bool,
+        debug_callback: Option<Box<DebugMessageFn>>,
+        required_extensions: impl Iterator<Item = &'a CStr>,
     ) -> Result<Self, DriverError> {
         // Required to enable non-uniform descriptor indexing (bindless)
         #[cfg(target_os = "macos")]
@@ -142,7 +159,10 @@ impl Instance {
         let entry = Entry::linked();
 
         let required_extensions = required_extensions.collect::<Vec<_>>();
-        let enabled_extension_names = unsafe { Self::extension_names(debug) };
+        let debug_utils_enabled =
+            Self::is_debug_utils_enabled(debug) && Self::is_debug_utils_supported(&entry);
+        let enabled_extension_names =
+            unsafe { Self::extension_names(debug, sync_validation, debug_utils_enabled) };
         let enabled_extension_strings: Vec<String> = enabled_extension_names
             .iter()
             .map(|&ptr| unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
@@ -163,7 +183,11 @@ impl Instance {
             .map(|raw_name| raw_name.as_ptr())
             .collect();
         let app_desc = vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_2);
-        let instance_desc = vk::InstanceCreateInfo::default()
+        let enabled_validation_features =
+            [vk::ValidationFeatureEnableEXT::SYNCHRONIZATION_VALIDATION];
+        let mut validation_features = vk::ValidationFeaturesEXT::default()
+            .enabled_validation_features(&enabled_validation_features);
+        let mut instance_desc = vk::InstanceCreateInfo::default()
             .application_info(&app_desc)
             .enabled_layer_names(&layer_names)
             .enabled_extension_names(&instance_extensions)
@@ -173,6 +197,10 @@ impl Instance {
                 vk::InstanceCreateFlags::default()
             });
 
+        if Self::is_sync_validation_enabled(debug, sync_validation) {
+            instance_desc = instance_desc.push_next(&mut validation_features);
+        }
+
         let instance = unsafe {
             entry.create_instance(&instance_desc, None).map_err(|_| {
                 if debug {
@@ -195,39 +223,46 @@ impl Instance {
 
         trace!("created a Vulkan instance");
 
+        let debug_utils =
+            debug_utils_enabled.then(|| ext::debug_utils::Instance::new(&entry, &instance));
+        let debug_message_fn = debug_callback.map(Box::new);
+
         #[cfg(target_os = "macos")]
-        let (debug_loader, debug_callback, debug_utils) = (None, None, None);
+        let debug_messenger = None;
 
         #[cfg(not(target_os = "macos"))]
-        let (debug_loader, debug_callback, debug_utils) = if debug {
-            let debug_info = vk::DebugReportCallbackCreateInfoEXT {
-                flags: vk::DebugReportFlagsEXT::ERROR
-                    | vk::DebugReportFlagsEXT::WARNING
-                    | vk::DebugReportFlagsEXT::PERFORMANCE_WARNING,
-                pfn_callback: Some(vulkan_debug_callback),
-                ..Default::default()
-            };
-
-            #[allow(deprecated)]
-            let debug_loader = ext::debug_report::Instance::new(&entry, &instance);
-
-            let debug_callback = unsafe {
-                #[allow(deprecated)]
-                debug_loader
-                    .create_debug_report_callback(&debug_info, None)
-                    .unwrap()
-            };
-
-            let debug_utils = ext::debug_utils::Instance::new(&entry, &instance);
-
-            (Some(debug_loader), Some(debug_callback), Some(debug_utils))
-        } else {
-            (None, None, None)
-        };
+        let debug_messenger = debug_utils
+            .as_ref()
+            .filter(|_| debug)
+            .and_then(|debug_utils| {
+                let mut create_info = vk::DebugUtilsMessengerCreateInfoEXT::default()
+                    .message_severity(
+                        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR
+                            | vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+                            | vk::DebugUtilsMessageSeverityFlagsEXT::INFO
+                            | vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE,
+                    )
+                    .message_type(
+                        vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+                            | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
+                            | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+                    )
+                    .pfn_user_callback(Some(vulkan_debug_callback));
+
+                if let Some(debug_message_fn) = &debug_message_fn {
+                    let debug_message_fn: *const Box<DebugMessageFn> = &**debug_message_fn;
+                    create_info = create_info.user_data(debug_message_fn.cast_mut().cast());
+                }
+
+                unsafe { debug_utils.create_debug_utils_messenger(&create_info, None) }
+                    .inspect_err(|err| warn!("unable to create debug messenger: {err}"))
+                    .ok()
+            });
 
         Ok(Self {
-            _debug_callback: debug_callback,
-            _debug_loader: debug_loader,
+            _debug_message_fn: debug_message_fn,
+            debug: debug && cfg!(not(target_os = "macos")),
+            debug_messenger,
             debug_utils,
             entry,
             instance,
@@ -248,8 +283,9 @@ impl Instance {
         let instance = unsafe { ash::Instance::load(entry.static_fn(), instance) };
 
         Ok(Self {
-            _debug_callback: None,
-            _debug_loader: None,
+            _debug_message_fn: None,
+            debug: false,
+            debug_messenger: None,
             debug_utils: None,
             entry,
             instance,
@@ -263,15 +299,17 @@ impl Instance {
     }
 
     unsafe fn extension_names(
-        #[cfg_attr(target_os = "macos", allow(unused_variables))] debug: bool,
+        debug: bool,
+        sync_validation: bool,
+        debug_utils: bool,
     ) -> Vec<*const c_char> {
-        #[cfg_attr(target_os = "macos", allow(unused_mut))]
         let mut res = vec![];
 
-        #[cfg(not(target_os = "macos"))]
-        if debug {
-            #[allow(deprecated)]
-            res.push(ext::debug_report::NAME.as_ptr());
+        if Self::is_sync_validation_enabled(debug, sync_validation) {
+            res.push(ext::validation_features::NAME.as_ptr());
+        }
+
+        if debug_utils {
             res.push(ext::debug_utils::NAME.as_ptr());
         }
 
@@ -286,9 +324,49 @@ impl Instance {
 
     /// Returns `true` if this instance was created with debug layers enabled.
     pub fn is_debug(this: &Self) -> bool {
+        this.debug
+    }
+
+    /// Returns `true` if debug labels and object names may be used with devices of this instance.
+    pub(crate) fn is_debug_utils(this: &Self) -> bool {
         this.debug_utils.is_some()
     }
 
+    /// Debug utilities are always enabled with the `debug-names` feature so that object names are
+    /// available in release builds; otherwise they require debug layers.
+    const fn is_debug_utils_enabled(debug: bool) -> bool {
+        cfg!(feature = "debug-names") || (debug && cfg!(not(target_os = "macos")))
+    }
+
+    /// Synchronization validation is provided by the validation layers, which only report messages
+    /// on platforms other than macOS.
+    const fn is_sync_validation_enabled(debug: bool, sync_validation: bool) -> bool {
+        debug && sync_validation && cfg!(not(target_os = "macos"))
+    }
+
+    /// Returns `true` if the Vulkan implementation provides `VK_EXT_debug_utils`; object names and
+    /// debug labels are silently skipped when it does not.
+    fn is_debug_utils_supported(entry: &Entry) -> bool {
+        let supported = unsafe { entry.enumerate_instance_extension_properties(None) }
+            .map(|extensions| {
+                extensions.iter().any(|extension| {
+                    extension
+                        .extension_name_as_c_str()
+                        .is_ok_and(|name| name == ext::debug_utils::NAME)
+                })
+            })
+            .unwrap_or_default();
+
+        if !supported {
+            warn!(
+                "{} is not supported",
+                ext::debug_utils::NAME.to_string_lossy()
+            );
+        }
+
+        supported
+    }
+
     /// Returns the names of all enabled instance extensions.
     pub fn enabled_extensions(this: &Self) -> &[String] {
         &this.enabled_extensions
@@ -365,13 +443,35 @@ impl Drop for Instance {
         }
 
         unsafe {
-            #[allow(deprecated)]
-            if let Some(debug_loader) = &self._debug_loader {
-                let debug_callback = self._debug_callback.unwrap();
-                debug_loader.destroy_debug_report_callback(debug_callback, None);
+            if let (Some(debug_utils), Some(debug_messenger)) =
+                (&self.debug_utils, self.debug_messenger)
+            {
+                debug_utils.destroy_debug_utils_messenger(debug_messenger, None);
             }
 
             self.instance.destroy_instance(None);
         }
     }
 }
+
+#[cfg(all(test, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+
+    type Severity = vk::DebugUtilsMessageSeverityFlagsEXT;
+
+    #[test]
+    pub fn debug_message_severity() {
+        assert_eq!(debug_message_level(Severity::ERROR, ""), Level::Error);
+        assert_eq!(debug_message_level(Severity::WARNING, ""), Level::Warn);
+        assert_eq!(debug_message_level(Severity::INFO, ""), Level::Debug);
+        assert_eq!(debug_message_level(Severity::VERBOSE, ""), Level::Trace);
+        assert_eq!(
+            debug_message_level(
+                Severity::ERROR,
+                "UNASSIGNED-BestPractices-pipeline-stage-flags"
+            ),
+            Level::Warn
+        );
+    }
+}
@@ -1109,6 +1109,12 @@ pub(super) const fn pipeline_stage_access_flags(
 /// help debugging the issue.
 #[derive(Debug)]
 pub enum DriverError {
+    /// The logical device has been lost, for example because of a driver update, reset or crash.
+    ///
+    /// The device and all resources created with it must be dropped; a new device may then be
+    /// created and resources loaded again.
+    DeviceLost,
+
     /// The input data, or referenced data, is not valid for the current state.
     InvalidData,
 
@@ -419,6 +419,7 @@ impl RenderPass {
             .subpass(subpass_idx)
             .vertex_input_state(&vertex_input_state)
             .viewport_state(&viewport_state);
+        let pipeline_name = pipeline.name.as_deref();
 
         let pipeline = unsafe {
             this.device.create_graphics_pipelines(
@@ -436,6 +437,10 @@ impl RenderPass {
             DriverError::Unsupported
         })?[0];
 
+        if let Some(name) = pipeline_name {
+            Device::set_debug_name(&this.device, pipeline, name);
+        }
+
         entry.insert(pipeline);
 
         Ok(pipeline)
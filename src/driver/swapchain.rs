@@ -346,7 +346,7 @@ impl Swapchain {
                 );
 
                 let image_idx = image_idx as u32;
-                image.name = Some(format!("swapchain{image_idx}"));
+                Image::set_name(&mut image, format!("swapchain{image_idx}"));
 
                 Ok(SwapchainImage {
                     exec_idx: 0,
@@ -0,0 +1,187 @@
+//! Shared records of the schedules resolved from render graphs.
+
+use {
+    super::{Binding, Pass, dot::write_dot},
+    ash::vk,
+    std::sync::Arc,
+};
+
+#[cfg(feature = "parking_lot")]
+use parking_lot::Mutex;
+
+#[cfg(not(feature = "parking_lot"))]
+use std::sync::Mutex;
+
+/// A shared record of the schedules of passes resolved from render graphs.
+///
+/// Captures are attached to graphs using
+/// [`RenderGraph::set_capture`](super::RenderGraph::set_capture) and are written while the graphs
+/// are resolved. Clones of a capture share the same record, so it may be read after a graph
+/// has been submitted or presented, including in release builds.
+#[derive(Clone, Debug, Default)]
+pub struct RenderCapture(Arc<Mutex<Capture>>);
+
+#[derive(Debug, Default)]
+struct Capture {
+    dot: String,
+    stats: RenderStats,
+}
+
+/// Counts of the work recorded from the schedules resolved from render graphs.
+///
+/// Only the commands recorded by the pass closures of a graph are counted; indirect draws and
+/// dispatches count as single commands because their parameters are read by the device.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct RenderStats {
+    /// The number of compute dispatch commands, including indirect dispatches.
+    pub dispatches: usize,
+
+    /// The number of draw commands, including indirect draws.
+    pub draw_calls: usize,
+
+    /// The number of descriptor pools and render passes leased to record passes.
+    pub leases: usize,
+
+    /// The number of passes recorded, after merging.
+    pub passes: usize,
+
+    /// The number of triangles drawn by direct draw commands using a triangle topology.
+    pub triangles: u64,
+
+    /// The number of bytes uploaded using
+    /// [`RenderGraph::update_buffer`](super::RenderGraph::update_buffer).
+    pub uploaded_bytes: vk::DeviceSize,
+
+    /// The number of vertices drawn by direct draw commands, across all instances.
+    pub vertices: u64,
+}
+
+impl RenderStats {
+    pub(super) fn add(&mut self, other: Self) {
+        self.dispatches += other.dispatches;
+        self.draw_calls += other.draw_calls;
+        self.leases += other.leases;
+        self.passes += other.passes;
+        self.triangles += other.triangles;
+        self.uploaded_bytes += other.uploaded_bytes;
+        self.vertices += other.vertices;
+    }
+
+    pub(super) fn record_draw(
+        &mut self,
+        topology: vk::PrimitiveTopology,
+        vertex_count: u32,
+        instance_count: u32,
+    ) {
+        let vertex_count = vertex_count as u64;
+        let instance_count = instance_count as u64;
+        let triangle_count = match topology {
+            vk::PrimitiveTopology::TRIANGLE_LIST => vertex_count / 3,
+            vk::PrimitiveTopology::TRIANGLE_STRIP | vk::PrimitiveTopology::TRIANGLE_FAN => {
+                vertex_count.saturating_sub(2)
+            }
+            vk::PrimitiveTopology::TRIANGLE_LIST_WITH_ADJACENCY => vertex_count / 6,
+            vk::PrimitiveTopology::TRIANGLE_STRIP_WITH_ADJACENCY => {
+                (vertex_count / 2).saturating_sub(2)
+            }
+            _ => 0,
+        };
+
+        self.draw_calls += 1;
+        self.triangles += triangle_count * instance_count;
+        self.vertices += vertex_count * instance_count;
+    }
+}
+
+impl RenderCapture {
+    /// Clears everything captured so far.
+    pub fn reset(&self) {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut capture = self.0.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut capture = capture.unwrap();
+
+        capture.dot.clear();
+        capture.stats = Default::default();
+    }
+
+    /// Returns counts of the work recorded from each schedule of passes resolved since this
+    /// capture was created or reset.
+    pub fn stats(&self) -> RenderStats {
+        let capture = self.0.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let capture = capture.unwrap();
+
+        capture.stats
+    }
+
+    /// Returns a [DOT](https://graphviz.org/doc/info/lang.html) description of each schedule of
+    /// passes resolved since this capture was created or reset.
+    ///
+    /// Each schedule is a separate `digraph`, such as one for the passes recorded by
+    /// [`Resolver::record_node`](super::Resolver::record_node) and another for the remaining
+    /// passes.
+    /// Passes are described in the order they were recorded, after reordering and merging, along
+    /// with the resources each pass accesses.
+    pub fn to_dot(&self) -> String {
+        let capture = self.0.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let capture = capture.unwrap();
+
+        capture.dot.clone()
+    }
+
+    pub(super) fn add_stats(&self, stats: RenderStats) {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut capture = self.0.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut capture = capture.unwrap();
+
+        capture.stats.add(stats);
+    }
+
+    pub(super) fn write_schedule<'a>(
+        &self,
+        bindings: &[Binding],
+        passes: impl IntoIterator<Item = (usize, &'a Pass)>,
+    ) {
+        #[cfg_attr(not(feature = "parking_lot"), allow(unused_mut))]
+        let mut capture = self.0.lock();
+
+        #[cfg(not(feature = "parking_lot"))]
+        let mut capture = capture.unwrap();
+
+        write_dot(&mut capture.dot, bindings, passes).expect("Unable to write DOT output");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn record_draw_counts() {
+        let mut stats = RenderStats::default();
+
+        stats.record_draw(vk::PrimitiveTopology::TRIANGLE_LIST, 6, 2);
+        stats.record_draw(vk::PrimitiveTopology::TRIANGLE_STRIP, 4, 1);
+        stats.record_draw(vk::PrimitiveTopology::LINE_LIST, 2, 1);
+
+        assert_eq!(stats.draw_calls, 3);
+        assert_eq!(stats.triangles, 6);
+        assert_eq!(stats.vertices, 18);
+
+        let mut total = RenderStats {
+            passes: 1,
+            ..Default::default()
+        };
+        total.add(stats);
+
+        assert_eq!(total.passes, 1);
+        assert_eq!(total.triangles, 6);
+    }
+}
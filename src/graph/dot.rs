@@ -0,0 +1,189 @@
+//! Graphviz DOT output of recorded passes and the resources they access.
+
+use {
+    super::{Binding, NodeIndex, Pass, pass_ref::Subresource},
+    crate::driver::{image::Image, image_access_layout, is_write_access},
+    std::{
+        collections::{BTreeSet, HashMap},
+        fmt::{Result, Write},
+    },
+    vk_sync::AccessType,
+};
+
+/// Writes a `digraph` of the given passes, in order, to `w`.
+///
+/// Each pass is a box which is linked to the next pass by a dotted edge. Each resource accessed by
+/// a pass is an ellipse; writes point from the pass to the resource and reads point from the
+/// resource to the pass. Image edges note the layout used by the access and the previous access
+/// whenever a barrier will be required between them.
+pub(super) fn write_dot<'a>(
+    w: &mut impl Write,
+    bindings: &[Binding],
+    passes: impl IntoIterator<Item = (usize, &'a Pass)>,
+) -> Result {
+    writeln!(w, "digraph render_graph {{")?;
+    writeln!(w, "    rankdir=LR;")?;
+
+    let mut nodes = BTreeSet::new();
+    let mut prev_accesses = HashMap::<NodeIndex, AccessType>::new();
+    let mut prev_pass_idx = None;
+
+    for (pass_idx, pass) in passes {
+        // The trailing execution of an unresolved pass has no function and is never recorded
+        let execs = pass
+            .execs
+            .iter()
+            .filter(|exec| exec.func.is_some())
+            .collect::<Vec<_>>();
+
+        write!(
+            w,
+            "    p{pass_idx} [shape=box label=\"[{pass_idx}] {}",
+            escape(&pass.name)
+        )?;
+
+        if execs.len() > 1 {
+            write!(w, "\\n{} subpasses", execs.len())?;
+        }
+
+        writeln!(w, "\"];")?;
+
+        if let Some(prev_pass_idx) = prev_pass_idx.replace(pass_idx) {
+            writeln!(w, "    p{prev_pass_idx} -> p{pass_idx} [style=dotted];")?;
+        }
+
+        for (exec_idx, exec) in execs.iter().enumerate() {
+            let mut accesses = exec.accesses.iter().collect::<Vec<_>>();
+            accesses.sort_unstable_by_key(|(node_idx, _)| **node_idx);
+
+            for (&node_idx, accesses) in accesses {
+                nodes.insert(node_idx);
+
+                for access in accesses {
+                    let mut label = String::new();
+
+                    if execs.len() > 1 {
+                        write!(label, "{exec_idx}: ")?;
+                    }
+
+                    write!(label, "{:?}", access.access)?;
+
+                    if matches!(access.subresource, Subresource::Image(_)) {
+                        write!(label, "\\n{:?}", image_access_layout(access.access))?;
+                    }
+
+                    if let Some(prev_access) =
+                        prev_accesses
+                            .insert(node_idx, access.access)
+                            .filter(|prev_access| {
+                                *prev_access != access.access || is_write_access(*prev_access)
+                            })
+                    {
+                        write!(label, "\\nafter {prev_access:?}")?;
+                    }
+
+                    if is_write_access(access.access) {
+                        writeln!(w, "    p{pass_idx} -> n{node_idx} [label=\"{label}\"];")?;
+                    } else {
+                        writeln!(w, "    n{node_idx} -> p{pass_idx} [label=\"{label}\"];")?;
+                    }
+                }
+            }
+        }
+    }
+
+    for node_idx in nodes {
+        write!(w, "    n{node_idx} [label=\"#{node_idx} ")?;
+
+        match &bindings[node_idx] {
+            Binding::AccelerationStructure(accel_struct, _) => write!(
+                w,
+                "acceleration structure\\n{} bytes",
+                accel_struct.info.size
+            )?,
+            Binding::AccelerationStructureLease(accel_struct, _) => write!(
+                w,
+                "acceleration structure (lease)\\n{} bytes",
+                accel_struct.info.size
+            )?,
+            Binding::Buffer(buffer, _) => {
+                write!(w, "buffer")?;
+                write_name(w, &buffer.name)?;
+                write!(w, "\\n{} bytes", buffer.info.size)?;
+            }
+            Binding::BufferLease(buffer, _) => {
+                write!(w, "buffer (lease)")?;
+                write_name(w, &buffer.name)?;
+                write!(w, "\\n{} bytes", buffer.info.size)?;
+            }
+            Binding::Image(image, _) => {
+                write!(w, "image")?;
+                write_image(w, image)?;
+            }
+            Binding::ImageLease(image, _) => {
+                write!(w, "image (lease)")?;
+                write_image(w, image)?;
+            }
+            Binding::SwapchainImage(image, _) => {
+                write!(w, "swapchain image")?;
+                write_image(w, image)?;
+            }
+        }
+
+        writeln!(w, "\"];")?;
+    }
+
+    writeln!(w, "}}")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn write_image(w: &mut impl Write, image: &Image) -> Result {
+    write_name(w, &image.name)?;
+    write!(
+        w,
+        "\\n{}x{}x{} {:?}",
+        image.info.width, image.info.height, image.info.depth, image.info.fmt
+    )
+}
+
+fn write_name(w: &mut impl Write, name: &Option<String>) -> Result {
+    if let Some(name) = name {
+        write!(w, " `{}`", escape(name))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::RenderGraph;
+
+    #[test]
+    pub fn write_dot_passes() {
+        let mut render_graph = RenderGraph::new();
+        render_graph
+            .begin_pass("clear \"first\"")
+            .record_cmd_buf(|_, _, _| {})
+            .submit_pass()
+            .begin_pass("second")
+            .record_cmd_buf(|_, _, _| {})
+            .record_cmd_buf(|_, _, _| {})
+            .submit_pass()
+            .begin_pass("unused")
+            .submit_pass();
+
+        assert_eq!(
+            render_graph.to_dot(),
+            "digraph render_graph {
+    rankdir=LR;
+    p0 [shape=box label=\"[0] clear \\\"first\\\"\"];
+    p1 [shape=box label=\"[1] second\\n2 subpasses\"];
+    p0 -> p1 [style=dotted];
+}
+"
+        );
+    }
+}
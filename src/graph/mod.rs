@@ -6,6 +6,8 @@ pub mod node;
 pub mod pass_ref;
 
 mod binding;
+mod capture;
+mod dot;
 mod edge;
 mod info;
 mod resolver;
@@ -13,6 +15,7 @@ mod swapchain;
 
 pub use self::{
     binding::{Bind, Unbind},
+    capture::{RenderCapture, RenderStats},
     resolver::Resolver,
 };
 
@@ -311,6 +314,7 @@ impl Pass {
 #[derive(Debug)]
 pub struct RenderGraph {
     bindings: Vec<Binding>,
+    capture: Option<RenderCapture>,
     passes: Vec<Pass>,
 
     /// Set to true (when in debug mode) in order to get a breakpoint hit where you want.
@@ -330,6 +334,7 @@ impl RenderGraph {
 
         Self {
             bindings,
+            capture: None,
             passes,
             #[cfg(debug_assertions)]
             debug,
@@ -946,6 +951,48 @@ impl RenderGraph {
         None
     }
 
+    /// Fills mip levels `1..` of an image by blitting each level into the next, starting with the
+    /// contents of mip level `0`.
+    ///
+    /// The image must have `TRANSFER_SRC` and `TRANSFER_DST` usage, and when `filter` is
+    /// `vk::Filter::LINEAR` its format must support linear filtering of blit sources.
+    pub fn generate_mip_levels(
+        &mut self,
+        image_node: impl Into<AnyImageNode>,
+        filter: vk::Filter,
+    ) -> &mut Self {
+        let image_node = image_node.into();
+        let image_info = self.node_info(image_node);
+        let aspect_mask = format_aspect_mask(image_info.fmt);
+        let mip_offset = |mip_level: u32| vk::Offset3D {
+            x: (image_info.width >> mip_level).max(1) as _,
+            y: (image_info.height >> mip_level).max(1) as _,
+            z: (image_info.depth >> mip_level).max(1) as _,
+        };
+        let mip_layers = |mip_level| vk::ImageSubresourceLayers {
+            aspect_mask,
+            mip_level,
+            base_array_layer: 0,
+            layer_count: image_info.array_layer_count,
+        };
+
+        for mip_level in 1..image_info.mip_level_count {
+            self.blit_image_region(
+                image_node,
+                image_node,
+                filter,
+                vk::ImageBlit {
+                    src_subresource: mip_layers(mip_level - 1),
+                    src_offsets: [vk::Offset3D::default(), mip_offset(mip_level - 1)],
+                    dst_subresource: mip_layers(mip_level),
+                    dst_offsets: [vk::Offset3D::default(), mip_offset(mip_level)],
+                },
+            );
+        }
+
+        self
+    }
+
     /// Returns the device address of a buffer node.
     ///
     /// # Panics
@@ -979,6 +1026,26 @@ impl RenderGraph {
         Resolver::new(self)
     }
 
+    /// Returns a [DOT](https://graphviz.org/doc/info/lang.html) description of the passes recorded
+    /// so far, in order, along with the resources each pass accesses.
+    ///
+    /// The output may be rendered using Graphviz, for example `dot -Tsvg graph.dot -o graph.svg`.
+    pub fn to_dot(&self) -> String {
+        let mut res = String::new();
+        dot::write_dot(&mut res, &self.bindings, self.passes.iter().enumerate())
+            .expect("Unable to write DOT output");
+
+        res
+    }
+
+    /// Records a description of each schedule of passes resolved from this graph into `capture`.
+    ///
+    /// See [`RenderCapture::to_dot`] for details.
+    pub fn set_capture(&mut self, capture: &RenderCapture) -> &mut Self {
+        self.capture = Some(capture.clone());
+        self
+    }
+
     /// Removes a node from this graph.
     ///
     /// Future access to `node` on this graph will return invalid results.
@@ -1026,9 +1093,15 @@ impl RenderGraph {
             .record_cmd_buf(move |device, cmd_buf, bindings| {
                 let buffer = *bindings[buffer_node];
 
+                let data = data.as_ref();
+
                 unsafe {
-                    device.cmd_update_buffer(cmd_buf, buffer, offset, data.as_ref());
+                    device.cmd_update_buffer(cmd_buf, buffer, offset, data);
                 }
+
+                bindings.update_stats(|stats| {
+                    stats.uploaded_bytes += data.len() as vk::DeviceSize;
+                });
             })
             .submit_pass()
     }
@@ -5,7 +5,7 @@ use {
         AccelerationStructureLeaseNode, AccelerationStructureNode, AnyAccelerationStructureNode,
         AnyBufferNode, AnyImageNode, Area, Attachment, Bind, Binding, BufferLeaseNode, BufferNode,
         ClearColorValue, Edge, Execution, ExecutionFunction, ExecutionPipeline, ImageLeaseNode,
-        ImageNode, Information, Node, NodeIndex, Pass, RenderGraph, SampleCount,
+        ImageNode, Information, Node, NodeIndex, Pass, RenderGraph, RenderStats, SampleCount,
         SwapchainImageNode,
     },
     crate::driver::{
@@ -27,7 +27,7 @@ use {
     ash::vk,
     log::trace,
     std::{
-        cell::RefCell,
+        cell::{Cell, RefCell},
         marker::PhantomData,
         ops::{Index, Range},
         sync::Arc,
@@ -1045,11 +1045,26 @@ bind!(RayTrace);
 pub struct Bindings<'a> {
     bindings: &'a [Binding],
     exec: &'a Execution,
+    stats: &'a Cell<RenderStats>,
 }
 
 impl<'a> Bindings<'a> {
-    pub(super) fn new(bindings: &'a [Binding], exec: &'a Execution) -> Self {
-        Self { bindings, exec }
+    pub(super) fn new(
+        bindings: &'a [Binding],
+        exec: &'a Execution,
+        stats: &'a Cell<RenderStats>,
+    ) -> Self {
+        Self {
+            bindings,
+            exec,
+            stats,
+        }
+    }
+
+    pub(super) fn update_stats(&self, f: impl FnOnce(&mut RenderStats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
     }
 
     fn binding_ref(&self, node_idx: usize) -> &Binding {
@@ -1245,6 +1260,8 @@ impl Compute<'_> {
                 .cmd_dispatch(self.cmd_buf, group_count_x, group_count_y, group_count_z);
         }
 
+        self.bindings.update_stats(|stats| stats.dispatches += 1);
+
         self
     }
 
@@ -1281,6 +1298,8 @@ impl Compute<'_> {
             );
         }
 
+        self.bindings.update_stats(|stats| stats.dispatches += 1);
+
         self
     }
 
@@ -1354,6 +1373,8 @@ impl Compute<'_> {
                 .cmd_dispatch_indirect(self.cmd_buf, *self.bindings[args_buf], args_offset);
         }
 
+        self.bindings.update_stats(|stats| stats.dispatches += 1);
+
         self
     }
 
@@ -1841,6 +1862,10 @@ impl Draw<'_> {
             );
         }
 
+        self.bindings.update_stats(|stats| {
+            stats.record_draw(self.pipeline.info.topology, vertex_count, instance_count)
+        });
+
         self
     }
 
@@ -1870,6 +1895,10 @@ impl Draw<'_> {
             );
         }
 
+        self.bindings.update_stats(|stats| {
+            stats.record_draw(self.pipeline.info.topology, index_count, instance_count)
+        });
+
         self
     }
 
@@ -1965,6 +1994,8 @@ impl Draw<'_> {
             );
         }
 
+        self.bindings.update_stats(|stats| stats.draw_calls += 1);
+
         self
     }
 
@@ -2005,6 +2036,8 @@ impl Draw<'_> {
             );
         }
 
+        self.bindings.update_stats(|stats| stats.draw_calls += 1);
+
         self
     }
 
@@ -2031,6 +2064,8 @@ impl Draw<'_> {
             );
         }
 
+        self.bindings.update_stats(|stats| stats.draw_calls += 1);
+
         self
     }
 
@@ -2062,6 +2097,8 @@ impl Draw<'_> {
             );
         }
 
+        self.bindings.update_stats(|stats| stats.draw_calls += 1);
+
         self
     }
 
@@ -1,6 +1,7 @@
 use {
     super::{
         Area, Attachment, Binding, Bindings, ExecutionPipeline, Node, NodeIndex, Pass, RenderGraph,
+        RenderStats,
         node::SwapchainImageNode,
         pass_ref::{Subresource, SubresourceAccess},
     },
@@ -12,6 +13,7 @@ use {
             SubpassDependency, SubpassInfo,
             accel_struct::AccelerationStructure,
             buffer::Buffer,
+            device::Device,
             format_aspect_mask,
             graphic::{DepthStencilMode, GraphicPipeline},
             image::{Image, ImageAccess},
@@ -27,7 +29,7 @@ use {
         debug, log_enabled, trace,
     },
     std::{
-        cell::RefCell,
+        cell::{Cell, RefCell},
         collections::{BTreeMap, HashMap, VecDeque},
         iter::repeat_n,
         ops::Range,
@@ -1688,10 +1690,16 @@ impl Resolver {
                     let layouts = pipeline.descriptor_info().layouts.values();
                     let mut descriptor_sets = Vec::with_capacity(layouts.len());
                     for descriptor_set_layout in layouts {
-                        descriptor_sets.push(DescriptorPool::allocate_descriptor_set(
+                        let descriptor_set = DescriptorPool::allocate_descriptor_set(
                             descriptor_pool,
                             descriptor_set_layout,
-                        )?);
+                        )?;
+                        Device::set_debug_name(
+                            &descriptor_pool.device,
+                            *descriptor_set,
+                            &pass.name,
+                        );
+                        descriptor_sets.push(descriptor_set);
                     }
                     exec_descriptor_sets.insert(exec_idx, descriptor_sets);
                 }
@@ -2407,8 +2415,32 @@ impl Resolver {
         // Optimize the schedule; leasing the required stuff it needs
         Self::reorder_scheduled_passes(schedule, end_pass_idx);
         self.merge_scheduled_passes(&mut schedule.passes);
+
+        if let Some(capture) = &self.graph.capture {
+            capture.write_schedule(
+                &self.graph.bindings,
+                schedule
+                    .passes
+                    .iter()
+                    .map(|&pass_idx| (pass_idx, &self.graph.passes[pass_idx])),
+            );
+        }
+
         self.lease_scheduled_resources(pool, &schedule.passes)?;
 
+        let stats = Cell::new(RenderStats {
+            leases: self
+                .physical_passes
+                .iter()
+                .map(|physical_pass| {
+                    physical_pass.descriptor_pool.is_some() as usize
+                        + physical_pass.render_pass.is_some() as usize
+                })
+                .sum(),
+            passes: schedule.passes.len(),
+            ..Default::default()
+        });
+
         for pass_idx in schedule.passes.iter().copied() {
             let pass = &mut self.graph.passes[pass_idx];
 
@@ -2520,7 +2552,7 @@ impl Resolver {
                     exec_func(
                         &cmd_buf.device,
                         **cmd_buf,
-                        Bindings::new(&self.graph.bindings, exec),
+                        Bindings::new(&self.graph.bindings, exec, &stats),
                     );
                 }
             }
@@ -2536,6 +2568,10 @@ impl Resolver {
             }
         }
 
+        if let Some(capture) = &self.graph.capture {
+            capture.add_stats(stats.get());
+        }
+
         thread_local! {
             static PASSES: RefCell<Vec<Pass>> = Default::default();
         }
@@ -2952,7 +2988,13 @@ impl Resolver {
                     from_ref(&vk::SubmitInfo::default().command_buffers(from_ref(&cmd_buf))),
                     cmd_buf.fence,
                 )
-                .map_err(|_| DriverError::OutOfMemory)?;
+                .map_err(|err| {
+                    if err == vk::Result::ERROR_DEVICE_LOST {
+                        DriverError::DeviceLost
+                    } else {
+                        DriverError::OutOfMemory
+                    }
+                })?;
         }
 
         cmd_buf.waiting = true;
@@ -341,7 +341,9 @@ pub mod prelude {
             ash::vk,
             buffer::{Buffer, BufferInfo, BufferInfoBuilder, BufferSubresourceRange},
             compute::{ComputePipeline, ComputePipelineInfo, ComputePipelineInfoBuilder},
-            device::{Device, DeviceInfo, DeviceInfoBuilder},
+            device::{
+                Device, DeviceInfo, DeviceInfoBuilder, MemoryCategory, MemoryReport, MemoryUsage,
+            },
             graphic::{
                 BlendMode, BlendModeBuilder, DepthStencilMode, DepthStencilModeBuilder,
                 GraphicPipeline, GraphicPipelineInfo, GraphicPipelineInfoBuilder, StencilMode,
@@ -371,7 +373,7 @@ pub mod prelude {
             },
         },
         graph::{
-            Bind, ClearColorValue, RenderGraph, Unbind,
+            Bind, ClearColorValue, RenderCapture, RenderGraph, RenderStats, Unbind,
             node::{
                 AccelerationStructureLeaseNode, AccelerationStructureNode,
                 AnyAccelerationStructureNode, AnyBufferNode, AnyImageNode, BufferLeaseNode,
@@ -380,7 +382,7 @@ pub mod prelude {
             pass_ref::{PassRef, PipelinePassRef},
         },
         pool::{
-            Lease, Pool, PoolInfo, PoolInfoBuilder,
+            Lease, Pool, PoolInfo, PoolInfoBuilder, PoolStats, ResourceCount,
             alias::{Alias, AliasPool},
             fifo::FifoPool,
             hash::HashPool,
@@ -1,7 +1,7 @@
 //! Pool which leases from a single bucket per resource type.
 
 use {
-    super::{Cache, Lease, Pool, PoolInfo, lease_command_buffer},
+    super::{Cache, Lease, Pool, PoolInfo, PoolStats, ResourceCount, lease_command_buffer},
     crate::driver::{
         CommandBuffer, CommandBufferInfo, DescriptorPool, DescriptorPoolInfo, DriverError,
         RenderPass, RenderPassInfo,
@@ -96,6 +96,18 @@ impl FifoPool {
     pub fn clear_images(&mut self) {
         self.image_cache = PoolInfo::explicit_cache(self.info.image_capacity);
     }
+
+    /// Returns the number of resources stored in and leased from this pool.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            accel_structs: ResourceCount::from_caches([&self.accel_struct_cache]),
+            buffers: ResourceCount::from_caches([&self.buffer_cache]),
+            command_buffers: ResourceCount::from_caches(self.command_buffer_cache.values()),
+            descriptor_pools: ResourceCount::from_caches([&self.descriptor_pool_cache]),
+            images: ResourceCount::from_caches([&self.image_cache]),
+            render_passes: ResourceCount::from_caches(self.render_pass_cache.values()),
+        }
+    }
 }
 
 impl Pool<AccelerationStructureInfo, AccelerationStructure> for FifoPool {
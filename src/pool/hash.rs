@@ -1,7 +1,7 @@
 //! Pool which leases by exactly matching the information before creating new resources.
 
 use {
-    super::{Cache, Lease, Pool, PoolInfo, lease_command_buffer},
+    super::{Cache, Lease, Pool, PoolInfo, PoolStats, ResourceCount, lease_command_buffer},
     crate::driver::{
         CommandBuffer, CommandBufferInfo, DescriptorPool, DescriptorPoolInfo, DriverError,
         RenderPass, RenderPassInfo,
@@ -77,6 +77,18 @@ impl HashPool {
         self.clear_buffers();
         self.clear_images();
     }
+
+    /// Returns the number of resources stored in and leased from this pool.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            accel_structs: ResourceCount::from_caches(self.acceleration_structure_cache.values()),
+            buffers: ResourceCount::from_caches(self.buffer_cache.values()),
+            command_buffers: ResourceCount::from_caches(self.command_buffer_cache.values()),
+            descriptor_pools: ResourceCount::from_caches(self.descriptor_pool_cache.values()),
+            images: ResourceCount::from_caches(self.image_cache.values()),
+            render_passes: ResourceCount::from_caches(self.render_pass_cache.values()),
+        }
+    }
 }
 
 macro_rules! resource_mgmt_fns {
@@ -1,7 +1,7 @@
 //! Pool which leases by looking for compatibile information before creating new resources.
 
 use {
-    super::{Cache, Lease, Pool, PoolInfo, lease_command_buffer},
+    super::{Cache, Lease, Pool, PoolInfo, PoolStats, ResourceCount, lease_command_buffer},
     crate::driver::{
         CommandBuffer, CommandBufferInfo, DescriptorPool, DescriptorPoolInfo, DriverError,
         RenderPass, RenderPassInfo,
@@ -154,6 +154,18 @@ impl LazyPool {
     {
         self.accel_struct_cache.retain(|&ty, _| f(ty))
     }
+
+    /// Returns the number of resources stored in and leased from this pool.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            accel_structs: ResourceCount::from_caches(self.accel_struct_cache.values()),
+            buffers: ResourceCount::from_caches(self.buffer_cache.values()),
+            command_buffers: ResourceCount::from_caches(self.command_buffer_cache.values()),
+            descriptor_pools: ResourceCount::from_caches([&self.descriptor_pool_cache]),
+            images: ResourceCount::from_caches(self.image_cache.values()),
+            render_passes: ResourceCount::from_caches(self.render_pass_cache.values()),
+        }
+    }
 }
 
 impl Pool<AccelerationStructureInfo, AccelerationStructure> for LazyPool {
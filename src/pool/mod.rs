@@ -100,9 +100,10 @@ use {
     },
     derive_builder::{Builder, UninitializedFieldError},
     std::{
-        fmt::Debug,
+        fmt::{Debug, Display, Formatter},
         mem::ManuallyDrop,
         ops::{Deref, DerefMut},
+        ptr::drop_in_place,
         sync::{Arc, Weak},
         thread::panicking,
     },
@@ -155,6 +156,19 @@ impl<T> Lease<T> {
             item: ManuallyDrop::new(item),
         }
     }
+
+    /// Consumes the lease and returns the leased resource, which will not be returned to the pool.
+    ///
+    /// This is useful for keeping resources, such as cached renders, for the lifetime of the
+    /// program.
+    pub fn detach(this: Self) -> T {
+        let mut this = ManuallyDrop::new(this);
+
+        unsafe {
+            drop_in_place(&mut this.cache_ref);
+            ManuallyDrop::take(&mut this.item)
+        }
+    }
 }
 
 impl<T> AsRef<T> for Lease<T> {
@@ -237,6 +251,94 @@ lease_builder!(AccelerationStructureInfo => AccelerationStructure);
 lease_builder!(BufferInfo => Buffer);
 lease_builder!(ImageInfo => Image);
 
+/// Number of resources held by a pool, by resource type.
+///
+/// The [`Display`] implementation is suitable for logging, for example:
+/// `info!("{}", pool.stats())`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct PoolStats {
+    /// Acceleration structure resources.
+    pub accel_structs: ResourceCount,
+
+    /// Buffer resources.
+    pub buffers: ResourceCount,
+
+    /// Command buffer resources.
+    pub command_buffers: ResourceCount,
+
+    /// Descriptor pool resources.
+    pub descriptor_pools: ResourceCount,
+
+    /// Image resources.
+    pub images: ResourceCount,
+
+    /// Render pass resources.
+    pub render_passes: ResourceCount,
+}
+
+impl Display for PoolStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, (name, count)) in [
+            ("acceleration structures", self.accel_structs),
+            ("buffers", self.buffers),
+            ("command buffers", self.command_buffers),
+            ("descriptor pools", self.descriptor_pools),
+            ("images", self.images),
+            ("render passes", self.render_passes),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "{name}: {} pooled, {} leased",
+                count.pooled, count.leased
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of resources of one type held by a pool.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ResourceCount {
+    /// Resources which are leased and will return to the pool when dropped.
+    ///
+    /// Leases taken before the resources of the pool were cleared, and detached leases, are not
+    /// counted.
+    pub leased: usize,
+
+    /// Resources stored in the pool and available to be leased.
+    pub pooled: usize,
+}
+
+impl ResourceCount {
+    fn from_caches<'a, T: 'a>(caches: impl IntoIterator<Item = &'a Cache<T>>) -> Self {
+        let mut res = Self::default();
+
+        for cache in caches {
+            // Each outstanding lease holds the only weak reference to its cache
+            res.leased += Arc::weak_count(cache);
+
+            let cache = cache.lock();
+
+            #[cfg(not(feature = "parking_lot"))]
+            let cache = cache.unwrap();
+
+            res.pooled += cache.len();
+        }
+
+        res
+    }
+}
+
 /// Information used to create a [`FifoPool`](self::fifo::FifoPool),
 /// [`HashPool`](self::hash::HashPool) or [`LazyPool`](self::lazy::LazyPool) instance.
 #[derive(Builder, Clone, Copy, Debug)]
@@ -345,3 +447,34 @@ impl From<UninitializedFieldError> for PoolInfoBuilderError {
         Self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn resource_count() {
+        let cache: Cache<u32> = PoolInfo::default_cache();
+        let lease = Lease::new(Arc::downgrade(&cache), 0);
+        let detached = Lease::new(Arc::downgrade(&cache), 1);
+
+        assert_eq!(
+            ResourceCount::from_caches([&cache]),
+            ResourceCount {
+                leased: 2,
+                pooled: 0
+            }
+        );
+
+        drop(lease);
+        Lease::detach(detached);
+
+        assert_eq!(
+            ResourceCount::from_caches([&cache]),
+            ResourceCount {
+                leased: 0,
+                pooled: 1
+            }
+        );
+    }
+}